@@ -0,0 +1,29 @@
+use std::error::Error;
+use std::fmt;
+
+/// The LexError type describes malformed data encountered by a lexer,
+/// such as an unterminated string, at the given character position.
+#[derive(PartialEq, Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct LexError {
+    pub message: String,
+    pub position: usize,
+}
+
+impl fmt::Display for LexError {
+    /// Formats the error as its message followed by its position.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use luthor::error::LexError;
+    ///
+    /// let error = LexError{ message: "unterminated string".to_string(), position: 4 };
+    /// assert_eq!(error.to_string(), "unterminated string at position 4");
+    /// ```
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} at position {}", self.message, self.position)
+    }
+}
+
+impl Error for LexError {}
@@ -0,0 +1,223 @@
+use std::collections::HashMap;
+use token::Token;
+use token::Category;
+
+// Returns the ANSI select graphic rendition parameters used
+// to color tokens of the given category, if they're colored.
+fn default_ansi_color(category: &Category) -> Option<&'static str> {
+    match *category {
+        Category::Keyword => Some("1;34"),
+        Category::String | Category::Code | Category::Addition => Some("32"),
+        Category::Regex | Category::Deletion => Some("31"),
+        Category::Comment => Some("90"),
+        Category::Integer | Category::Float | Category::Boolean | Category::DateTime => Some("35"),
+        Category::Directive => Some("35"),
+        Category::Variable | Category::Tag | Category::Selector => Some("36"),
+        Category::Attribute | Category::Property | Category::ListMarker => Some("33"),
+        Category::Operator | Category::AssignmentOperator => Some("33"),
+        Category::Heading => Some("1;35"),
+        Category::Emphasis => Some("3"),
+        Category::Link => Some("4;34"),
+        _ => None,
+    }
+}
+
+/// Highlights the given tokens for display in a terminal, wrapping each
+/// lexeme in an ANSI color escape sequence chosen by its category.
+/// Text, whitespace and other uncolored categories are left as-is.
+///
+/// # Examples
+///
+/// ```
+/// use luthor::highlight::highlight_ansi;
+/// use luthor::token::Category;
+///
+/// let mut lexer = luthor::tokenizer::new("fn luthor");
+/// lexer.tokenize_next(2, Category::Keyword);
+/// lexer.tokenize_next(7, Category::Text);
+/// assert_eq!(highlight_ansi(lexer.tokens_ref()), "\x1b[1;34mfn\x1b[0m luthor");
+/// ```
+pub fn highlight_ansi(tokens: &[Token]) -> String {
+    highlight_ansi_with(tokens, &HashMap::new())
+}
+
+/// Highlights the given tokens like `highlight_ansi`, using the
+/// given ANSI select graphic rendition parameters (e.g. "1;31")
+/// in place of the default colors for the categories it maps.
+///
+/// # Examples
+///
+/// ```
+/// use std::collections::HashMap;
+/// use luthor::highlight::highlight_ansi_with;
+/// use luthor::token::Category;
+///
+/// let mut colors = HashMap::new();
+/// colors.insert(Category::Text, "31");
+///
+/// let mut lexer = luthor::tokenizer::new("luthor");
+/// lexer.tokenize_next(6, Category::Text);
+/// assert_eq!(highlight_ansi_with(lexer.tokens_ref(), &colors), "\x1b[31mluthor\x1b[0m");
+/// ```
+pub fn highlight_ansi_with(tokens: &[Token], colors: &HashMap<Category, &str>) -> String {
+    let mut highlighted = String::new();
+
+    for token in tokens {
+        let color = colors.get(&token.category).cloned()
+            .or_else(|| default_ansi_color(&token.category));
+
+        match color {
+            Some(color) => {
+                highlighted.push_str(&format!("\x1b[{}m{}\x1b[0m", color, token.lexeme));
+            },
+            None => highlighted.push_str(&token.lexeme),
+        }
+    }
+
+    highlighted
+}
+
+// Derives an HTML class name from the given category,
+// converting its name to kebab case (e.g. tok-list-marker).
+fn html_class(category: &Category) -> String {
+    let mut class = String::from("tok");
+
+    for (index, c) in category.to_string().char_indices() {
+        if c.is_uppercase() || index == 0 {
+            class.push('-');
+        }
+        class.extend(c.to_lowercase());
+    }
+
+    class
+}
+
+// Escapes the characters with special meaning in HTML element content.
+fn escape_html(data: &str) -> String {
+    let mut escaped = String::with_capacity(data.len());
+
+    for c in data.chars() {
+        match c {
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '&' => escaped.push_str("&amp;"),
+            _ => escaped.push(c),
+        }
+    }
+
+    escaped
+}
+
+/// Highlights the given tokens for display in a web page, wrapping each
+/// escaped lexeme in a span element with a class derived from its category
+/// (e.g. tok-keyword). Whitespace is escaped but left unwrapped.
+///
+/// # Examples
+///
+/// ```
+/// use luthor::highlight::highlight_html;
+/// use luthor::token::Category;
+///
+/// let mut lexer = luthor::tokenizer::new("fn a<b>");
+/// lexer.tokenize_next(2, Category::Keyword);
+/// lexer.skip_whitespace();
+/// lexer.tokenize_next(4, Category::Text);
+/// assert_eq!(
+///     highlight_html(lexer.tokens_ref()),
+///     "<span class=\"tok-keyword\">fn</span> <span class=\"tok-text\">a&lt;b&gt;</span>"
+/// );
+/// ```
+pub fn highlight_html(tokens: &[Token]) -> String {
+    let mut highlighted = String::new();
+
+    for token in tokens {
+        if token.category == Category::Whitespace {
+            highlighted.push_str(&escape_html(&token.lexeme));
+        } else {
+            highlighted.push_str(&format!(
+                "<span class=\"{}\">{}</span>",
+                html_class(&token.category),
+                escape_html(&token.lexeme)
+            ));
+        }
+    }
+
+    highlighted
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+    use super::highlight_ansi;
+    use super::highlight_ansi_with;
+    use super::highlight_html;
+    use lexers::rust;
+    use token::Category;
+
+    // Removes ANSI color escape sequences from the given data.
+    fn strip_escapes(data: &str) -> String {
+        let mut stripped = String::new();
+        let mut escaped = false;
+
+        for c in data.chars() {
+            if c == '\x1b' {
+                escaped = true;
+            } else if escaped {
+                escaped = c != 'm';
+            } else {
+                stripped.push(c);
+            }
+        }
+
+        stripped
+    }
+
+    #[test]
+    fn highlight_ansi_wraps_keywords_in_escapes() {
+        let data = "fn main() { let s = \"élégant\"; } // ok";
+        let highlighted = highlight_ansi(&rust::lex(data));
+
+        assert!(highlighted.starts_with("\x1b[1;34mfn\x1b[0m main"));
+        assert!(highlighted.contains("\x1b[32m\"élégant\"\x1b[0m"));
+        assert!(highlighted.ends_with("\x1b[90m// ok\x1b[0m"));
+        assert_eq!(strip_escapes(&highlighted), data);
+    }
+
+    #[test]
+    fn highlight_ansi_with_overrides_the_default_colors() {
+        let mut colors = HashMap::new();
+        colors.insert(Category::Keyword, "1;31");
+        let highlighted = highlight_ansi_with(&rust::lex("fn main"), &colors);
+
+        assert_eq!(highlighted, "\x1b[1;31mfn\x1b[0m main");
+    }
+
+    #[test]
+    fn highlight_html_escapes_lexemes() {
+        let mut lexer = ::tokenizer::new("a < b && c > d");
+        lexer.consume_while(|_| true);
+        lexer.tokenize(Category::Text);
+
+        assert_eq!(
+            highlight_html(lexer.tokens_ref()),
+            "<span class=\"tok-text\">a &lt; b &amp;&amp; c &gt; d</span>"
+        );
+    }
+
+    #[test]
+    fn highlight_html_derives_classes_from_categories() {
+        let mut lexer = ::tokenizer::new("fn=-x");
+        lexer.tokenize_next(2, Category::Keyword);
+        lexer.tokenize_next(1, Category::AssignmentOperator);
+        lexer.tokenize_next(1, Category::ListMarker);
+        lexer.tokenize_next(1, Category::Custom("sql-hint"));
+
+        assert_eq!(
+            highlight_html(lexer.tokens_ref()),
+            "<span class=\"tok-keyword\">fn</span>\
+             <span class=\"tok-assignment-operator\">=</span>\
+             <span class=\"tok-list-marker\">-</span>\
+             <span class=\"tok-sql-hint\">x</span>"
+        );
+    }
+}
@@ -0,0 +1,323 @@
+use tokenizer::new;
+use tokenizer::Tokenizer;
+use tokenizer::StateFunction;
+use token::Token;
+use token::Category;
+
+const KEYWORDS: [&str; 44] = [
+    "auto", "break", "case", "char", "const", "continue", "default", "do",
+    "double", "else", "enum", "extern", "float", "for", "goto", "if", "inline",
+    "int", "long", "register", "restrict", "return", "short", "signed",
+    "sizeof", "static", "struct", "switch", "typedef", "union", "unsigned",
+    "void", "volatile", "while", "_Alignas", "_Alignof", "_Atomic", "_Bool",
+    "_Complex", "_Generic", "_Imaginary", "_Noreturn", "_Static_assert",
+    "_Thread_local",
+];
+
+// Ordered so that longer operators are matched before their prefixes.
+const OPERATORS: [&str; 38] = [
+    "<<=", ">>=", "...", "->", "++", "--", "<<", ">>", "<=", ">=", "==", "!=",
+    "&&", "||", "+=", "-=", "*=", "/=", "%=", "&=", "^=", "|=", "+", "-", "*",
+    "/", "%", "&", "|", "^", "~", "!", "<", ">", "=", "?", ":", ".",
+];
+
+fn is_identifier_start(c: char) -> bool {
+    c.is_alphabetic() || c == '_'
+}
+
+fn is_identifier_char(c: char) -> bool {
+    c.is_alphanumeric() || c == '_'
+}
+
+fn initial_state(lexer: &mut Tokenizer) -> Option<StateFunction> {
+    let c = lexer.current_char()?;
+
+    match c {
+        '{' | '}' => lexer.tokenize_char(Category::Brace),
+        '[' | ']' => lexer.tokenize_char(Category::Bracket),
+        '(' | ')' => lexer.tokenize_char(Category::Parenthesis),
+        ';' | ',' => lexer.tokenize_char(Category::Punctuation),
+        '"' | '\'' => {
+            lexer.tokenize_string(c, Category::String);
+        },
+        '#' if at_line_start(lexer) => directive(lexer),
+        _ => {
+            if c.is_whitespace() {
+                lexer.skip_whitespace();
+            } else if c.is_ascii_digit() ||
+                (c == '.' && lexer.peek_char().is_some_and(|c| c.is_ascii_digit())) {
+                number(lexer);
+            } else if is_identifier_start(c) {
+                lexer.consume_while(is_identifier_char);
+                match lexer.current_lexeme() {
+                    "true" | "false" => lexer.tokenize(Category::Boolean),
+                    lexeme if KEYWORDS.contains(&lexeme) => lexer.tokenize(Category::Keyword),
+                    _ => lexer.tokenize(Category::Identifier),
+                }
+            } else if !lexer.tokenize_line_comment("//", Category::Comment) &&
+                !lexer.tokenize_block_comment("/*", "*/", false, Category::Comment) &&
+                !lexer.tokenize_match(&OPERATORS, Category::Operator) {
+                lexer.tokenize_char(Category::Text);
+            }
+        }
+    }
+
+    Some(StateFunction::new(initial_state))
+}
+
+// Determines whether or not the current position is preceded
+// only by whitespace on its line, where directives can appear.
+fn at_line_start(lexer: &Tokenizer) -> bool {
+    match lexer.last_token() {
+        Some(token) => token.category == Category::Whitespace && token.lexeme.contains('\n'),
+        None => true,
+    }
+}
+
+// Lexes a preprocessor directive, which ends with the line it's on,
+// unless the line ends with a backslash to continue the directive.
+// Comments within it are lexed as such; a block comment can span
+// lines, in which case the directive ends with the comment.
+fn directive(lexer: &mut Tokenizer) {
+    loop {
+        skip_line_whitespace(lexer);
+        while let Some(c) = lexer.current_char() {
+            if c == '\n' || lexer.starts_with("//") || lexer.starts_with("/*") {
+                break;
+            }
+            lexer.advance();
+            if c == '\\' {
+                lexer.accept("\r");
+                lexer.accept("\n");
+            } else if c == '"' {
+                // Comment delimiters in strings (e.g. include paths) are literal.
+                while let Some(c) = lexer.current_char() {
+                    if c == '\n' {
+                        break;
+                    }
+                    lexer.advance();
+                    if c == '"' {
+                        break;
+                    } else if c == '\\' {
+                        lexer.advance();
+                    }
+                }
+            }
+        }
+
+        // Whitespace between the directive and a comment isn't part of it.
+        if lexer.current_char() == Some('/') {
+            let trailing_whitespace = lexer.current_lexeme().chars().rev()
+                .take_while(|&c| c != '\n' && c.is_whitespace())
+                .count();
+            for _ in 0..trailing_whitespace {
+                lexer.backup();
+            }
+        }
+        lexer.tokenize(Category::Directive);
+        skip_line_whitespace(lexer);
+
+        if lexer.tokenize_line_comment("//", Category::Comment) ||
+            !lexer.tokenize_block_comment("/*", "*/", false, Category::Comment) ||
+            lexer.last_token().is_some_and(|token| token.lexeme.contains('\n')) {
+            return;
+        }
+    }
+}
+
+// Tokenizes whitespace at the current position, up to the end of the line.
+fn skip_line_whitespace(lexer: &mut Tokenizer) {
+    lexer.consume_while(|c| c != '\n' && c.is_whitespace());
+    lexer.tokenize(Category::Whitespace);
+}
+
+fn number(lexer: &mut Tokenizer) {
+    let mut category = Category::Integer;
+
+    if lexer.current_char() == Some('0') && lexer.peek_char().is_some_and(|c| c == 'x' || c == 'X') {
+        lexer.advance_by(2);
+        lexer.consume_while(|c| c.is_ascii_hexdigit());
+    } else {
+        lexer.consume_while(|c| c.is_ascii_digit());
+        if lexer.current_char() == Some('.') {
+            lexer.advance();
+            lexer.consume_while(|c| c.is_ascii_digit());
+            category = Category::Float;
+        }
+        if lexer.current_char().is_some_and(|c| c == 'e' || c == 'E') {
+            let exponent_start = match lexer.peek_char() {
+                Some('+') | Some('-') => 2,
+                _ => 1,
+            };
+            if lexer.peek(exponent_start).is_some_and(|c| c.is_ascii_digit()) {
+                lexer.advance_by(exponent_start);
+                lexer.consume_while(|c| c.is_ascii_digit());
+                category = Category::Float;
+            }
+        }
+    }
+
+    // Type suffixes (e.g. 10UL or 1.5f) are part of the number.
+    lexer.consume_while(|c| "uUlLfF".contains(c));
+    lexer.tokenize(category);
+}
+
+/// Lexes the given C source, categorizing keywords, identifiers, numbers,
+/// string and character literals, comments and operators. Preprocessor
+/// directives are categorized as Category::Directive, including any lines
+/// they're continued onto with a trailing backslash, apart from comments.
+///
+/// # Examples
+///
+/// ```
+/// use luthor::lexers::c;
+/// use luthor::token::Category;
+///
+/// let tokens = c::lex("#include <stdio.h>\nint main;");
+/// assert_eq!(tokens[0].lexeme, "#include <stdio.h>");
+/// assert_eq!(tokens[0].category, Category::Directive);
+/// assert_eq!(tokens[2].category, Category::Keyword);
+/// ```
+pub fn lex(data: &str) -> Vec<Token> {
+    let mut lexer = new(data);
+    lexer.run(StateFunction::new(initial_state));
+    lexer.into_tokens()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::lex;
+    use token::Category;
+
+    #[test]
+    fn it_can_handle_directives_with_line_continuations() {
+        let tokens = lex("#define MAX(a, b) \\\n  ((a) > (b))\n  # undef MIN\nx = a # b;");
+        let expected_tokens = [
+            ("#define MAX(a, b) \\\n  ((a) > (b))", Category::Directive),
+            ("\n  ", Category::Whitespace),
+            ("# undef MIN", Category::Directive),
+            ("\n", Category::Whitespace),
+            ("x", Category::Identifier),
+            (" ", Category::Whitespace),
+            ("=", Category::Operator),
+            (" ", Category::Whitespace),
+            ("a", Category::Identifier),
+            (" ", Category::Whitespace),
+            ("#", Category::Text),
+            (" ", Category::Whitespace),
+            ("b", Category::Identifier),
+            (";", Category::Punctuation),
+        ];
+
+        assert_eq!(tokens.len(), expected_tokens.len());
+        for (index, token) in tokens.iter().enumerate() {
+            assert_eq!(token.lexeme, expected_tokens[index].0);
+            assert_eq!(token.category, expected_tokens[index].1);
+        }
+    }
+
+    #[test]
+    fn it_can_handle_directives_with_trailing_line_comments() {
+        let tokens = lex("#include \"a//b.h\" // io\n#define A 1");
+        let expected_tokens = [
+            ("#include \"a//b.h\"", Category::Directive),
+            (" ", Category::Whitespace),
+            ("// io", Category::Comment),
+            ("\n", Category::Whitespace),
+            ("#define A 1", Category::Directive),
+        ];
+
+        assert_eq!(tokens.len(), expected_tokens.len());
+        for (index, token) in tokens.iter().enumerate() {
+            assert_eq!(token.lexeme, expected_tokens[index].0);
+            assert_eq!(token.category, expected_tokens[index].1);
+        }
+    }
+
+    #[test]
+    fn it_can_handle_directives_with_block_comments() {
+        let tokens = lex("#define A /* one */ 1 /* multi\n line */\nint y;");
+        let expected_tokens = [
+            ("#define A", Category::Directive),
+            (" ", Category::Whitespace),
+            ("/* one */", Category::Comment),
+            (" ", Category::Whitespace),
+            ("1", Category::Directive),
+            (" ", Category::Whitespace),
+            ("/* multi\n line */", Category::Comment),
+            ("\n", Category::Whitespace),
+            ("int", Category::Keyword),
+            (" ", Category::Whitespace),
+            ("y", Category::Identifier),
+            (";", Category::Punctuation),
+        ];
+
+        assert_eq!(tokens.len(), expected_tokens.len());
+        for (index, token) in tokens.iter().enumerate() {
+            assert_eq!(token.lexeme, expected_tokens[index].0);
+            assert_eq!(token.category, expected_tokens[index].1);
+        }
+    }
+
+    #[test]
+    fn it_can_handle_numbers() {
+        let tokens = lex("unsigned x = 0xFFul + 017 + 1.5e-3f;");
+        let expected_tokens = [
+            ("unsigned", Category::Keyword),
+            (" ", Category::Whitespace),
+            ("x", Category::Identifier),
+            (" ", Category::Whitespace),
+            ("=", Category::Operator),
+            (" ", Category::Whitespace),
+            ("0xFFul", Category::Integer),
+            (" ", Category::Whitespace),
+            ("+", Category::Operator),
+            (" ", Category::Whitespace),
+            ("017", Category::Integer),
+            (" ", Category::Whitespace),
+            ("+", Category::Operator),
+            (" ", Category::Whitespace),
+            ("1.5e-3f", Category::Float),
+            (";", Category::Punctuation),
+        ];
+
+        assert_eq!(tokens.len(), expected_tokens.len());
+        for (index, token) in tokens.iter().enumerate() {
+            assert_eq!(token.lexeme, expected_tokens[index].0);
+            assert_eq!(token.category, expected_tokens[index].1);
+        }
+    }
+
+    #[test]
+    fn it_can_handle_comments_and_literals() {
+        let tokens = lex("/* a\n * b */ char c = '\\''; // \"d\"\ns->t(\"\\\"\");");
+        let expected_tokens = [
+            ("/* a\n * b */", Category::Comment),
+            (" ", Category::Whitespace),
+            ("char", Category::Keyword),
+            (" ", Category::Whitespace),
+            ("c", Category::Identifier),
+            (" ", Category::Whitespace),
+            ("=", Category::Operator),
+            (" ", Category::Whitespace),
+            ("'\\''", Category::String),
+            (";", Category::Punctuation),
+            (" ", Category::Whitespace),
+            ("// \"d\"", Category::Comment),
+            ("\n", Category::Whitespace),
+            ("s", Category::Identifier),
+            ("->", Category::Operator),
+            ("t", Category::Identifier),
+            ("(", Category::Parenthesis),
+            ("\"\\\"\"", Category::String),
+            (")", Category::Parenthesis),
+            (";", Category::Punctuation),
+        ];
+
+        assert_eq!(tokens.len(), expected_tokens.len());
+        for (index, token) in tokens.iter().enumerate() {
+            assert_eq!(token.lexeme, expected_tokens[index].0);
+            assert_eq!(token.category, expected_tokens[index].1);
+        }
+    }
+}
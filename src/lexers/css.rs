@@ -0,0 +1,383 @@
+use tokenizer::new;
+use tokenizer::Tokenizer;
+use tokenizer::StateFunction;
+use token::Token;
+use token::Category;
+
+fn is_name_char(c: char) -> bool {
+    c.is_alphanumeric() || c == '-' || c == '_'
+}
+
+// Determines whether the upcoming data is a selector, by checking if it
+// opens a block before it ends a declaration. This supports nested rules,
+// such as those in media queries, without tracking the nesting depth.
+fn selector_follows(lexer: &Tokenizer) -> bool {
+    let mut remaining = lexer.remaining();
+    while let Some(index) = remaining.find(['{', ';', '}', '/']) {
+        remaining = &remaining[index..];
+        if remaining.starts_with("/*") {
+            match remaining.find("*/") {
+                Some(end) => remaining = &remaining[end + 2..],
+                None => return false,
+            }
+        } else if remaining.starts_with('/') {
+            remaining = &remaining[1..];
+        } else {
+            return remaining.starts_with('{');
+        }
+    }
+
+    false
+}
+
+fn comment(lexer: &mut Tokenizer) {
+    lexer.advance_by(2);
+    while lexer.has_more_data() && !lexer.starts_with("*/") {
+        lexer.advance();
+    }
+    lexer.advance_by(2);
+    lexer.tokenize(Category::Comment);
+}
+
+fn string(lexer: &mut Tokenizer) {
+    let quote = lexer.current_char().unwrap();
+    lexer.advance();
+    while let Some(c) = lexer.current_char() {
+        lexer.advance();
+        if c == quote {
+            break;
+        } else if c == '\\' {
+            lexer.advance();
+        }
+    }
+    lexer.tokenize(Category::String);
+}
+
+fn initial_state(lexer: &mut Tokenizer) -> Option<StateFunction> {
+    match lexer.current_char() {
+        Some(c) => {
+            match c {
+                '{' | '}' => lexer.tokenize_next(1, Category::Brace),
+                ';' => lexer.tokenize_next(1, Category::Punctuation),
+                '@' => {
+                    lexer.advance();
+                    lexer.consume_while(is_name_char);
+                    lexer.tokenize(Category::Keyword);
+                    return Some(StateFunction::new(selector));
+                },
+                _ => {
+                    if c.is_whitespace() {
+                        lexer.skip_whitespace();
+                    } else if lexer.starts_with("/*") {
+                        comment(lexer);
+                    } else if selector_follows(lexer) {
+                        return Some(StateFunction::new(selector));
+                    } else {
+                        return Some(StateFunction::new(property));
+                    }
+                }
+            }
+
+            Some(StateFunction::new(initial_state))
+        }
+
+        None => None
+    }
+}
+
+fn selector(lexer: &mut Tokenizer) -> Option<StateFunction> {
+    match lexer.current_char() {
+        Some(c) => {
+            match c {
+                '{' | ';' | '}' => return Some(StateFunction::new(initial_state)),
+                '#' | '.' | ':' | ',' => lexer.tokenize_next(1, Category::Punctuation),
+                '>' | '+' | '~' | '=' => lexer.tokenize_next(1, Category::Operator),
+                '[' | ']' => lexer.tokenize_next(1, Category::Bracket),
+                '(' | ')' => lexer.tokenize_next(1, Category::Parenthesis),
+                '"' | '\'' => string(lexer),
+                '*' => lexer.tokenize_next(1, Category::Selector),
+                _ => {
+                    if c.is_whitespace() {
+                        lexer.skip_whitespace();
+                    } else if lexer.starts_with("/*") {
+                        comment(lexer);
+                    } else if is_name_char(c) {
+                        lexer.consume_while(is_name_char);
+                        lexer.tokenize(Category::Selector);
+                    } else {
+                        lexer.tokenize_next(1, Category::Text);
+                    }
+                }
+            }
+
+            Some(StateFunction::new(selector))
+        }
+
+        None => None
+    }
+}
+
+fn property(lexer: &mut Tokenizer) -> Option<StateFunction> {
+    lexer.consume_while(|c| is_name_char(c) || c == '*');
+    if lexer.current_lexeme().is_empty() {
+        lexer.tokenize_next(1, Category::Text);
+        return Some(StateFunction::new(initial_state));
+    }
+    lexer.tokenize(Category::Property);
+
+    loop {
+        match lexer.current_char() {
+            Some(':') => {
+                lexer.tokenize_next(1, Category::AssignmentOperator);
+                return Some(StateFunction::new(value));
+            },
+            Some(c) if c.is_whitespace() => lexer.skip_whitespace(),
+            _ if lexer.starts_with("/*") => comment(lexer),
+            _ => return Some(StateFunction::new(initial_state)),
+        }
+    }
+}
+
+fn value(lexer: &mut Tokenizer) -> Option<StateFunction> {
+    match lexer.current_char() {
+        Some(c) => {
+            match c {
+                ';' | '}' => return Some(StateFunction::new(initial_state)),
+                ',' | '/' if !lexer.starts_with("/*") => {
+                    lexer.tokenize_next(1, Category::Punctuation);
+                },
+                '(' | ')' => lexer.tokenize_next(1, Category::Parenthesis),
+                '"' | '\'' => string(lexer),
+                '#' => {
+                    lexer.advance();
+                    lexer.consume_while(|c| c.is_ascii_hexdigit());
+                    lexer.tokenize(Category::Integer);
+                },
+                '!' => {
+                    lexer.advance();
+                    lexer.consume_while(is_name_char);
+                    lexer.tokenize(Category::Keyword);
+                },
+                _ => {
+                    if c.is_whitespace() {
+                        lexer.skip_whitespace();
+                    } else if lexer.starts_with("/*") {
+                        comment(lexer);
+                    } else if c.is_ascii_digit() || ((c == '.' || c == '-' || c == '+') &&
+                        lexer.peek_char().is_some_and(|c| c.is_ascii_digit() || c == '.')) {
+                        number(lexer);
+                    } else if is_name_char(c) {
+                        lexer.consume_while(is_name_char);
+                        if lexer.current_lexeme() == "url" && lexer.current_char() == Some('(') {
+                            lexer.tokenize(Category::Identifier);
+                            lexer.tokenize_next(1, Category::Parenthesis);
+                            url(lexer);
+                        } else {
+                            lexer.tokenize(Category::Identifier);
+                        }
+                    } else {
+                        lexer.tokenize_next(1, Category::Text);
+                    }
+                }
+            }
+
+            Some(StateFunction::new(value))
+        }
+
+        None => None
+    }
+}
+
+fn number(lexer: &mut Tokenizer) {
+    let mut category = Category::Integer;
+    lexer.accept("+-");
+    lexer.consume_while(|c| c.is_ascii_digit());
+    if lexer.accept(".") {
+        lexer.consume_while(|c| c.is_ascii_digit());
+        category = Category::Float;
+    }
+
+    // Units, such as px or %, are part of the number.
+    if !lexer.accept("%") {
+        lexer.consume_while(|c| c.is_alphabetic());
+    }
+    lexer.tokenize(category);
+}
+
+// Scans the contents of a url function, which are often left unquoted
+// and can contain parentheses and semicolons of their own.
+fn url(lexer: &mut Tokenizer) {
+    lexer.skip_whitespace();
+    if lexer.current_char().is_some_and(|c| c == '"' || c == '\'') {
+        string(lexer);
+    } else {
+        let mut depth = 0;
+        while let Some(c) = lexer.current_char() {
+            if c == '(' {
+                depth += 1;
+            } else if c == ')' {
+                if depth == 0 {
+                    break;
+                }
+                depth -= 1;
+            }
+            lexer.advance();
+        }
+        lexer.tokenize(Category::String);
+    }
+    lexer.skip_whitespace();
+    if lexer.current_char() == Some(')') {
+        lexer.tokenize_next(1, Category::Parenthesis);
+    }
+}
+
+/// Lexes the given CSS data, categorizing selectors, property names,
+/// values, comments, and the punctuation separating them.
+///
+/// # Examples
+///
+/// ```
+/// use luthor::lexers::css;
+/// use luthor::token::Category;
+///
+/// let tokens = css::lex("p { color: red }");
+/// assert_eq!(tokens[0].lexeme, "p");
+/// assert_eq!(tokens[0].category, Category::Selector);
+/// assert_eq!(tokens[4].lexeme, "color");
+/// assert_eq!(tokens[4].category, Category::Property);
+/// ```
+pub fn lex(data: &str) -> Vec<Token> {
+    let mut lexer = new(data);
+    lexer.run(StateFunction::new(initial_state));
+    lexer.into_tokens()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::lex;
+    use token::Category;
+
+    #[test]
+    fn it_can_handle_rule_blocks() {
+        let tokens = lex("#main > .item:hover, a {\n  margin: 0 1.5em;\n}");
+        let expected_tokens = [
+            ("#", Category::Punctuation),
+            ("main", Category::Selector),
+            (" ", Category::Whitespace),
+            (">", Category::Operator),
+            (" ", Category::Whitespace),
+            (".", Category::Punctuation),
+            ("item", Category::Selector),
+            (":", Category::Punctuation),
+            ("hover", Category::Selector),
+            (",", Category::Punctuation),
+            (" ", Category::Whitespace),
+            ("a", Category::Selector),
+            (" ", Category::Whitespace),
+            ("{", Category::Brace),
+            ("\n  ", Category::Whitespace),
+            ("margin", Category::Property),
+            (":", Category::AssignmentOperator),
+            (" ", Category::Whitespace),
+            ("0", Category::Integer),
+            (" ", Category::Whitespace),
+            ("1.5em", Category::Float),
+            (";", Category::Punctuation),
+            ("\n", Category::Whitespace),
+            ("}", Category::Brace),
+        ];
+
+        assert_eq!(tokens.len(), expected_tokens.len());
+        for (index, token) in tokens.iter().enumerate() {
+            assert_eq!(token.lexeme, expected_tokens[index].0);
+            assert_eq!(token.category, expected_tokens[index].1);
+        }
+    }
+
+    #[test]
+    fn it_can_handle_comments_inside_declarations() {
+        let tokens = lex("p{color:/* {;} */red}");
+        let expected_tokens = [
+            ("p", Category::Selector),
+            ("{", Category::Brace),
+            ("color", Category::Property),
+            (":", Category::AssignmentOperator),
+            ("/* {;} */", Category::Comment),
+            ("red", Category::Identifier),
+            ("}", Category::Brace),
+        ];
+
+        assert_eq!(tokens.len(), expected_tokens.len());
+        for (index, token) in tokens.iter().enumerate() {
+            assert_eq!(token.lexeme, expected_tokens[index].0);
+            assert_eq!(token.category, expected_tokens[index].1);
+        }
+    }
+
+    #[test]
+    fn it_can_handle_hex_colors() {
+        let tokens = lex("a{color:#FF00aa !important}");
+        let expected_tokens = [
+            ("a", Category::Selector),
+            ("{", Category::Brace),
+            ("color", Category::Property),
+            (":", Category::AssignmentOperator),
+            ("#FF00aa", Category::Integer),
+            (" ", Category::Whitespace),
+            ("!important", Category::Keyword),
+            ("}", Category::Brace),
+        ];
+
+        assert_eq!(tokens.len(), expected_tokens.len());
+        for (index, token) in tokens.iter().enumerate() {
+            assert_eq!(token.lexeme, expected_tokens[index].0);
+            assert_eq!(token.category, expected_tokens[index].1);
+        }
+    }
+
+    #[test]
+    fn it_can_handle_urls_with_parentheses() {
+        let tokens = lex("a{background:url(img(1).png;v=2)}");
+        let expected_tokens = [
+            ("a", Category::Selector),
+            ("{", Category::Brace),
+            ("background", Category::Property),
+            (":", Category::AssignmentOperator),
+            ("url", Category::Identifier),
+            ("(", Category::Parenthesis),
+            ("img(1).png;v=2", Category::String),
+            (")", Category::Parenthesis),
+            ("}", Category::Brace),
+        ];
+
+        assert_eq!(tokens.len(), expected_tokens.len());
+        for (index, token) in tokens.iter().enumerate() {
+            assert_eq!(token.lexeme, expected_tokens[index].0);
+            assert_eq!(token.category, expected_tokens[index].1);
+        }
+    }
+
+    #[test]
+    fn it_can_handle_nested_rules() {
+        let tokens = lex("@media print{p{margin:0}}");
+        let expected_tokens = [
+            ("@media", Category::Keyword),
+            (" ", Category::Whitespace),
+            ("print", Category::Selector),
+            ("{", Category::Brace),
+            ("p", Category::Selector),
+            ("{", Category::Brace),
+            ("margin", Category::Property),
+            (":", Category::AssignmentOperator),
+            ("0", Category::Integer),
+            ("}", Category::Brace),
+            ("}", Category::Brace),
+        ];
+
+        assert_eq!(tokens.len(), expected_tokens.len());
+        for (index, token) in tokens.iter().enumerate() {
+            assert_eq!(token.lexeme, expected_tokens[index].0);
+            assert_eq!(token.category, expected_tokens[index].1);
+        }
+    }
+}
@@ -0,0 +1,125 @@
+use tokenizer::new;
+use tokenizer::Tokenizer;
+use tokenizer::StateFunction;
+use token::Token;
+use token::Category;
+
+fn field(lexer: &mut Tokenizer) -> Option<StateFunction> {
+    match lexer.current_char()? {
+        ',' => lexer.tokenize_next(1, Category::Punctuation),
+        '\r' | '\n' => {
+            lexer.accept("\r");
+            lexer.accept("\n");
+            lexer.tokenize(Category::Whitespace);
+        },
+        '"' => return Some(StateFunction::new(quoted_field)),
+        _ => {
+            // Quotes are only special at the start of a field.
+            lexer.consume_until(|c| c == ',' || c == '\r' || c == '\n');
+            lexer.tokenize(Category::Text);
+        }
+    }
+
+    Some(StateFunction::new(field))
+}
+
+fn quoted_field(lexer: &mut Tokenizer) -> Option<StateFunction> {
+    lexer.advance();
+
+    // Quoted fields can contain delimiters and newlines, with
+    // quotes escaped by doubling them (e.g. "say ""hi""").
+    while lexer.advance_to('"') {
+        lexer.advance();
+        if !lexer.accept("\"") {
+            break;
+        }
+    }
+    lexer.tokenize(Category::String);
+
+    Some(StateFunction::new(field))
+}
+
+/// Lexes the given CSV data, categorizing quoted fields as strings,
+/// other fields as text, and their delimiters as punctuation.
+/// Line breaks between records are categorized as whitespace.
+///
+/// # Examples
+///
+/// ```
+/// use luthor::lexers::csv;
+/// use luthor::token::Category;
+///
+/// let tokens = csv::lex("id,\"name, full\"");
+/// assert_eq!(tokens[2].lexeme, "\"name, full\"");
+/// assert_eq!(tokens[2].category, Category::String);
+/// ```
+pub fn lex(data: &str) -> Vec<Token> {
+    let mut lexer = new(data);
+    lexer.run(StateFunction::new(field));
+    lexer.into_tokens()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::lex;
+    use token::Category;
+
+    #[test]
+    fn it_can_handle_quoted_fields_with_commas() {
+        let tokens = lex("a b,\"c, d\",,e\r\nf");
+        let expected_tokens = [
+            ("a b", Category::Text),
+            (",", Category::Punctuation),
+            ("\"c, d\"", Category::String),
+            (",", Category::Punctuation),
+            (",", Category::Punctuation),
+            ("e", Category::Text),
+            ("\r\n", Category::Whitespace),
+            ("f", Category::Text),
+        ];
+
+        assert_eq!(tokens.len(), expected_tokens.len());
+        for (index, token) in tokens.iter().enumerate() {
+            assert_eq!(token.lexeme, expected_tokens[index].0);
+            assert_eq!(token.category, expected_tokens[index].1);
+        }
+    }
+
+    #[test]
+    fn it_can_handle_escaped_quotes() {
+        let tokens = lex("\"say \"\"hi\"\"\",x\"y\",\"\"\"\"");
+        let expected_tokens = [
+            ("\"say \"\"hi\"\"\"", Category::String),
+            (",", Category::Punctuation),
+            ("x\"y\"", Category::Text),
+            (",", Category::Punctuation),
+            ("\"\"\"\"", Category::String),
+        ];
+
+        assert_eq!(tokens.len(), expected_tokens.len());
+        for (index, token) in tokens.iter().enumerate() {
+            assert_eq!(token.lexeme, expected_tokens[index].0);
+            assert_eq!(token.category, expected_tokens[index].1);
+        }
+    }
+
+    #[test]
+    fn it_can_handle_fields_spanning_newlines() {
+        let tokens = lex("1,\"two\nlines\"\n2,\"open");
+        let expected_tokens = [
+            ("1", Category::Text),
+            (",", Category::Punctuation),
+            ("\"two\nlines\"", Category::String),
+            ("\n", Category::Whitespace),
+            ("2", Category::Text),
+            (",", Category::Punctuation),
+            ("\"open", Category::String),
+        ];
+
+        assert_eq!(tokens.len(), expected_tokens.len());
+        for (index, token) in tokens.iter().enumerate() {
+            assert_eq!(token.lexeme, expected_tokens[index].0);
+            assert_eq!(token.category, expected_tokens[index].1);
+        }
+    }
+}
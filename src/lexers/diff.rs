@@ -0,0 +1,179 @@
+use tokenizer::new;
+use tokenizer::Tokenizer;
+use tokenizer::StateFunction;
+use token::Token;
+use token::Category;
+
+// Creates a state that lexes a line, given the number of old and new lines
+// remaining in the current hunk. Within a hunk, lines starting with "---"
+// or "+++" are removed or added lines, rather than file headers.
+fn line(old_lines: usize, new_lines: usize) -> StateFunction {
+    StateFunction::new(move |lexer: &mut Tokenizer| {
+        let c = lexer.current_char()?;
+        let mut old_lines = old_lines;
+        let mut new_lines = new_lines;
+        let in_hunk = old_lines > 0 || new_lines > 0;
+
+        if c == '\n' {
+            lexer.tokenize_next(1, Category::Whitespace);
+            return Some(line(old_lines, new_lines));
+        }
+
+        if lexer.starts_with("@@") {
+            let (old_count, new_count) = hunk_counts(lexer.remaining());
+            old_lines = old_count;
+            new_lines = new_count;
+
+            // The closing marker may be followed by the enclosing section.
+            lexer.advance_by(2);
+            while lexer.has_more_data() && !lexer.starts_with("@@") && lexer.current_char() != Some('\n') {
+                lexer.advance();
+            }
+            if lexer.starts_with("@@") {
+                lexer.advance_by(2);
+            }
+            lexer.tokenize(Category::Heading);
+            lexer.consume_until(|c| c == '\n');
+            lexer.tokenize(Category::Text);
+            return Some(line(old_lines, new_lines));
+        }
+
+        let category = match c {
+            '-' | '+' if !in_hunk && (lexer.starts_with("---") || lexer.starts_with("+++")) => Category::Heading,
+            '-' => {
+                old_lines = old_lines.saturating_sub(1);
+                Category::Deletion
+            },
+            '+' => {
+                new_lines = new_lines.saturating_sub(1);
+                Category::Addition
+            },
+            '\\' => Category::Comment,
+            _ => {
+                if c == ' ' {
+                    old_lines = old_lines.saturating_sub(1);
+                    new_lines = new_lines.saturating_sub(1);
+                }
+                Category::Text
+            }
+        };
+        lexer.consume_until(|c| c == '\n');
+        lexer.tokenize(category);
+
+        Some(line(old_lines, new_lines))
+    })
+}
+
+// Parses the old and new line counts from a hunk header
+// (e.g. "@@ -1,3 +1,4 @@"), which default to one if omitted.
+fn hunk_counts(header: &str) -> (usize, usize) {
+    let header = header.lines().next().unwrap_or("");
+    let mut old_count = 0;
+    let mut new_count = 0;
+
+    for range in header.split_whitespace().skip(1).take(2) {
+        let count = match range.split_once(',') {
+            Some((_, count)) => count.parse().unwrap_or(0),
+            None => 1,
+        };
+        if range.starts_with('-') {
+            old_count = count;
+        } else if range.starts_with('+') {
+            new_count = count;
+        }
+    }
+
+    (old_count, new_count)
+}
+
+/// Lexes the given unified diff, categorizing added and removed lines,
+/// file headers and hunk headers (as headings), and context lines (as text).
+/// Hunk line counts are tracked so that removed or added lines which start
+/// with "---" or "+++" aren't mistaken for file headers.
+///
+/// # Examples
+///
+/// ```
+/// use luthor::lexers::diff;
+/// use luthor::token::Category;
+///
+/// let tokens = diff::lex("@@ -1 +1 @@\n-old\n+new");
+/// assert_eq!(tokens[0].category, Category::Heading);
+/// assert_eq!(tokens[2].category, Category::Deletion);
+/// assert_eq!(tokens[4].category, Category::Addition);
+/// ```
+pub fn lex(data: &str) -> Vec<Token> {
+    let mut lexer = new(data);
+    lexer.run(line(0, 0));
+    lexer.into_tokens()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::lex;
+    use token::Category;
+
+    #[test]
+    fn it_can_handle_added_lines() {
+        let tokens = lex("--- a/x\n+++ b/x\n@@ -1 +1,2 @@\n keep\n+++added\n");
+        let expected_tokens = [
+            ("--- a/x", Category::Heading),
+            ("\n", Category::Whitespace),
+            ("+++ b/x", Category::Heading),
+            ("\n", Category::Whitespace),
+            ("@@ -1 +1,2 @@", Category::Heading),
+            ("\n", Category::Whitespace),
+            (" keep", Category::Text),
+            ("\n", Category::Whitespace),
+            ("+++added", Category::Addition),
+            ("\n", Category::Whitespace),
+        ];
+
+        assert_eq!(tokens.len(), expected_tokens.len());
+        for (index, token) in tokens.iter().enumerate() {
+            assert_eq!(token.lexeme, expected_tokens[index].0);
+            assert_eq!(token.category, expected_tokens[index].1);
+        }
+    }
+
+    #[test]
+    fn it_can_handle_removed_lines() {
+        let tokens = lex("@@ -1,2 +0,0 @@\n-x\n--- y\n\\ No newline at end of file\n--- a/z");
+        let expected_tokens = [
+            ("@@ -1,2 +0,0 @@", Category::Heading),
+            ("\n", Category::Whitespace),
+            ("-x", Category::Deletion),
+            ("\n", Category::Whitespace),
+            ("--- y", Category::Deletion),
+            ("\n", Category::Whitespace),
+            ("\\ No newline at end of file", Category::Comment),
+            ("\n", Category::Whitespace),
+            ("--- a/z", Category::Heading),
+        ];
+
+        assert_eq!(tokens.len(), expected_tokens.len());
+        for (index, token) in tokens.iter().enumerate() {
+            assert_eq!(token.lexeme, expected_tokens[index].0);
+            assert_eq!(token.category, expected_tokens[index].1);
+        }
+    }
+
+    #[test]
+    fn it_can_handle_hunk_headers_with_sections() {
+        let tokens = lex("@@ -10,1 +10,1 @@ fn main() {\n-a\n+b");
+        let expected_tokens = [
+            ("@@ -10,1 +10,1 @@", Category::Heading),
+            (" fn main() {", Category::Text),
+            ("\n", Category::Whitespace),
+            ("-a", Category::Deletion),
+            ("\n", Category::Whitespace),
+            ("+b", Category::Addition),
+        ];
+
+        assert_eq!(tokens.len(), expected_tokens.len());
+        for (index, token) in tokens.iter().enumerate() {
+            assert_eq!(token.lexeme, expected_tokens[index].0);
+            assert_eq!(token.category, expected_tokens[index].1);
+        }
+    }
+}
@@ -0,0 +1,215 @@
+use tokenizer::new;
+use tokenizer::Tokenizer;
+use tokenizer::StateFunction;
+use token::Token;
+use token::Category;
+
+const INSTRUCTIONS: [&str; 18] = [
+    "ADD", "ARG", "CMD", "COPY", "ENTRYPOINT", "ENV", "EXPOSE", "FROM",
+    "HEALTHCHECK", "LABEL", "MAINTAINER", "ONBUILD", "RUN", "SHELL",
+    "STOPSIGNAL", "USER", "VOLUME", "WORKDIR",
+];
+
+// Lexes the start of a line, which is either a comment or an instruction.
+fn line_start(lexer: &mut Tokenizer) -> Option<StateFunction> {
+    let c = lexer.current_char()?;
+
+    if c.is_whitespace() {
+        lexer.skip_whitespace();
+    } else if !lexer.tokenize_line_comment("#", Category::Comment) {
+        lexer.consume_while(|c| c.is_alphabetic());
+        if !INSTRUCTIONS.iter().any(|instruction| lexer.tokenize_keyword_ci(instruction, Category::Keyword)) {
+            lexer.tokenize(Category::Text);
+        }
+        return Some(StateFunction::new(arguments));
+    }
+
+    Some(StateFunction::new(line_start))
+}
+
+// Lexes an instruction's arguments, which end with the line they're on,
+// unless the line ends with a backslash to continue the instruction.
+fn arguments(lexer: &mut Tokenizer) -> Option<StateFunction> {
+    let c = match lexer.current_char() {
+        Some(c) => c,
+        None => {
+            lexer.tokenize(Category::Text);
+            return None;
+        }
+    };
+
+    match c {
+        '\n' => return Some(StateFunction::new(line_start)),
+        '"' | '\'' => {
+            lexer.tokenize_string(c, Category::String);
+        },
+        '$' if variable_follows(lexer) => {
+            lexer.tokenize(Category::Text);
+            lexer.advance();
+            if lexer.current_char() == Some('{') {
+                lexer.advance_to('}');
+                lexer.advance();
+            } else {
+                lexer.consume_while(|c| c.is_alphanumeric() || c == '_');
+            }
+            lexer.tokenize(Category::Variable);
+        },
+        '\\' if continuation_follows(lexer) => {
+            lexer.tokenize_char(Category::Punctuation);
+
+            // Comment lines within a continued instruction are ignored by Docker.
+            loop {
+                lexer.skip_whitespace();
+                if !lexer.tokenize_line_comment("#", Category::Comment) {
+                    break;
+                }
+            }
+        },
+        _ => {
+            if c.is_whitespace() {
+                lexer.tokenize(Category::Text);
+                lexer.consume_while(|c| c != '\n' && c.is_whitespace());
+                lexer.tokenize(Category::Whitespace);
+            } else {
+                lexer.advance();
+                if c == '\\' {
+                    lexer.advance();
+                }
+            }
+        }
+    }
+
+    Some(StateFunction::new(arguments))
+}
+
+fn variable_follows(lexer: &Tokenizer) -> bool {
+    lexer.peek_char().is_some_and(|c| c == '{' || c == '_' || c.is_alphabetic())
+}
+
+// Determines whether or not the backslash at the current position
+// is followed by the end of the line, ignoring trailing whitespace.
+fn continuation_follows(lexer: &Tokenizer) -> bool {
+    let mut offset = 1;
+    loop {
+        match lexer.peek(offset) {
+            Some('\n') | None => return true,
+            Some(c) if c.is_whitespace() => offset += 1,
+            Some(_) => return false,
+        }
+    }
+}
+
+/// Lexes the given Dockerfile, categorizing instructions (in any case)
+/// as keywords, comments, quoted strings and variable references.
+/// Other arguments are categorized as text, and the backslashes that
+/// continue instructions onto the following line as punctuation.
+///
+/// # Examples
+///
+/// ```
+/// use luthor::lexers::dockerfile;
+/// use luthor::token::Category;
+///
+/// let tokens = dockerfile::lex("FROM rust:1.75");
+/// assert_eq!(tokens[0].category, Category::Keyword);
+/// assert_eq!(tokens[2].lexeme, "rust:1.75");
+/// assert_eq!(tokens[2].category, Category::Text);
+/// ```
+pub fn lex(data: &str) -> Vec<Token> {
+    let mut lexer = new(data);
+    lexer.run(StateFunction::new(line_start));
+    lexer.into_tokens()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::lex;
+    use token::Category;
+
+    #[test]
+    fn it_can_handle_instructions() {
+        let tokens = lex("from rust:1.75 AS build\nCOPY --from=build \"/app\" $HOME");
+        let expected_tokens = [
+            ("from", Category::Keyword),
+            (" ", Category::Whitespace),
+            ("rust:1.75", Category::Text),
+            (" ", Category::Whitespace),
+            ("AS", Category::Text),
+            (" ", Category::Whitespace),
+            ("build", Category::Text),
+            ("\n", Category::Whitespace),
+            ("COPY", Category::Keyword),
+            (" ", Category::Whitespace),
+            ("--from=build", Category::Text),
+            (" ", Category::Whitespace),
+            ("\"/app\"", Category::String),
+            (" ", Category::Whitespace),
+            ("$HOME", Category::Variable),
+        ];
+
+        assert_eq!(tokens.len(), expected_tokens.len());
+        for (index, token) in tokens.iter().enumerate() {
+            assert_eq!(token.lexeme, expected_tokens[index].0);
+            assert_eq!(token.category, expected_tokens[index].1);
+        }
+    }
+
+    #[test]
+    fn it_can_handle_continued_instructions() {
+        let tokens = lex("RUN apt-get update && \\\n    # cache\n    run ${X}\nUSER app");
+        let expected_tokens = [
+            ("RUN", Category::Keyword),
+            (" ", Category::Whitespace),
+            ("apt-get", Category::Text),
+            (" ", Category::Whitespace),
+            ("update", Category::Text),
+            (" ", Category::Whitespace),
+            ("&&", Category::Text),
+            (" ", Category::Whitespace),
+            ("\\", Category::Punctuation),
+            ("\n    ", Category::Whitespace),
+            ("# cache", Category::Comment),
+            ("\n    ", Category::Whitespace),
+            ("run", Category::Text),
+            (" ", Category::Whitespace),
+            ("${X}", Category::Variable),
+            ("\n", Category::Whitespace),
+            ("USER", Category::Keyword),
+            (" ", Category::Whitespace),
+            ("app", Category::Text),
+        ];
+
+        assert_eq!(tokens.len(), expected_tokens.len());
+        for (index, token) in tokens.iter().enumerate() {
+            assert_eq!(token.lexeme, expected_tokens[index].0);
+            assert_eq!(token.category, expected_tokens[index].1);
+        }
+    }
+
+    #[test]
+    fn it_can_handle_comments() {
+        let tokens = lex("# syntax=docker/dockerfile:1\n  EXPOSE 80 # not a comment\n");
+        let expected_tokens = [
+            ("# syntax=docker/dockerfile:1", Category::Comment),
+            ("\n  ", Category::Whitespace),
+            ("EXPOSE", Category::Keyword),
+            (" ", Category::Whitespace),
+            ("80", Category::Text),
+            (" ", Category::Whitespace),
+            ("#", Category::Text),
+            (" ", Category::Whitespace),
+            ("not", Category::Text),
+            (" ", Category::Whitespace),
+            ("a", Category::Text),
+            (" ", Category::Whitespace),
+            ("comment", Category::Text),
+            ("\n", Category::Whitespace),
+        ];
+
+        assert_eq!(tokens.len(), expected_tokens.len());
+        for (index, token) in tokens.iter().enumerate() {
+            assert_eq!(token.lexeme, expected_tokens[index].0);
+            assert_eq!(token.category, expected_tokens[index].1);
+        }
+    }
+}
@@ -0,0 +1,241 @@
+use tokenizer::new;
+use tokenizer::Tokenizer;
+use tokenizer::StateFunction;
+use token::Token;
+use token::Category;
+
+const KEYWORDS: [&str; 26] = [
+    "break", "case", "chan", "const", "continue", "default", "defer", "else",
+    "fallthrough", "for", "func", "go", "goto", "if", "import", "interface",
+    "map", "nil", "package", "range", "return", "select", "struct", "switch",
+    "type", "var",
+];
+
+// Ordered so that longer operators are matched before their prefixes.
+const OPERATORS: [&str; 40] = [
+    "&^=", "<<=", ">>=", "...", "&&", "||", "<-", "++", "--", "==", "!=",
+    "<=", ">=", ":=", "+=", "-=", "*=", "/=", "%=", "&=", "|=", "^=", "<<",
+    ">>", "&^", "+", "-", "*", "/", "%", "&", "|", "^", "<", ">", "=", "!",
+    ":", ".", "~",
+];
+
+fn is_identifier_start(c: char) -> bool {
+    c.is_alphabetic() || c == '_'
+}
+
+fn is_identifier_char(c: char) -> bool {
+    c.is_alphanumeric() || c == '_'
+}
+
+fn initial_state(lexer: &mut Tokenizer) -> Option<StateFunction> {
+    let c = lexer.current_char()?;
+
+    match c {
+        '{' | '}' => lexer.tokenize_next(1, Category::Brace),
+        '[' | ']' => lexer.tokenize_next(1, Category::Bracket),
+        '(' | ')' => lexer.tokenize_next(1, Category::Parenthesis),
+        ';' | ',' => lexer.tokenize_next(1, Category::Punctuation),
+        '"' | '\'' => quoted(lexer, c),
+        '`' => {
+            // Raw strings can span lines, and don't support escapes.
+            lexer.advance();
+            lexer.advance_to('`');
+            lexer.advance();
+            lexer.tokenize(Category::String);
+        },
+        _ => {
+            if c.is_whitespace() {
+                lexer.skip_whitespace();
+            } else if lexer.starts_with("//") {
+                lexer.consume_until(|c| c == '\n');
+                lexer.tokenize(Category::Comment);
+            } else if lexer.starts_with("/*") {
+                lexer.advance_by(2);
+                while lexer.has_more_data() && !lexer.starts_with("*/") {
+                    lexer.advance();
+                }
+                lexer.advance_by(2);
+                lexer.tokenize(Category::Comment);
+            } else if c.is_ascii_digit() ||
+                (c == '.' && lexer.peek_char().is_some_and(|c| c.is_ascii_digit())) {
+                number(lexer);
+            } else if is_identifier_start(c) {
+                lexer.consume_while(is_identifier_char);
+                match lexer.current_lexeme() {
+                    "true" | "false" => lexer.tokenize(Category::Boolean),
+                    lexeme if KEYWORDS.contains(&lexeme) => lexer.tokenize(Category::Keyword),
+                    _ => lexer.tokenize(Category::Identifier),
+                }
+            } else if !lexer.tokenize_match(&OPERATORS, Category::Operator) {
+                lexer.tokenize_next(1, Category::Text);
+            }
+        }
+    }
+
+    Some(StateFunction::new(initial_state))
+}
+
+// Lexes an interpreted string or rune literal, both of
+// which support escapes and end with the line they're on.
+fn quoted(lexer: &mut Tokenizer, quote: char) {
+    lexer.advance();
+    while let Some(c) = lexer.current_char() {
+        if c == '\n' {
+            break;
+        }
+        lexer.advance();
+        if c == quote {
+            break;
+        } else if c == '\\' {
+            lexer.advance();
+        }
+    }
+    lexer.tokenize(Category::String);
+}
+
+fn number(lexer: &mut Tokenizer) {
+    let mut category = Category::Integer;
+
+    if lexer.current_char() == Some('0') && lexer.peek_char().is_some_and(|c| "xXoObB".contains(c)) {
+        lexer.advance_by(2);
+        lexer.consume_while(|c| c.is_ascii_hexdigit() || c == '_');
+    } else {
+        lexer.consume_while(|c| c.is_ascii_digit() || c == '_');
+        if lexer.current_char() == Some('.') {
+            lexer.advance();
+            lexer.consume_while(|c| c.is_ascii_digit() || c == '_');
+            category = Category::Float;
+        }
+        if lexer.current_char().is_some_and(|c| c == 'e' || c == 'E') {
+            let exponent_start = match lexer.peek_char() {
+                Some('+') | Some('-') => 2,
+                _ => 1,
+            };
+            if lexer.peek(exponent_start).is_some_and(|c| c.is_ascii_digit()) {
+                lexer.advance_by(exponent_start);
+                lexer.consume_while(|c| c.is_ascii_digit() || c == '_');
+                category = Category::Float;
+            }
+        }
+    }
+
+    // Imaginary numbers are suffixed with an i.
+    if lexer.accept("i") {
+        category = Category::Float;
+    }
+    lexer.tokenize(category);
+}
+
+/// Lexes the given Go source, categorizing keywords, identifiers,
+/// numbers, interpreted and raw strings, runes, comments and operators.
+///
+/// # Examples
+///
+/// ```
+/// use luthor::lexers::go;
+/// use luthor::token::Category;
+///
+/// let tokens = go::lex("package main");
+/// assert_eq!(tokens[0].category, Category::Keyword);
+/// assert_eq!(tokens[2].category, Category::Identifier);
+/// ```
+pub fn lex(data: &str) -> Vec<Token> {
+    let mut lexer = new(data);
+    lexer.run(StateFunction::new(initial_state));
+    lexer.into_tokens()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::lex;
+    use token::Category;
+
+    #[test]
+    fn it_can_handle_raw_strings() {
+        let tokens = lex("s := `a\\n\"b\"\nc` + \"\\\"d\"");
+        let expected_tokens = [
+            ("s", Category::Identifier),
+            (" ", Category::Whitespace),
+            (":=", Category::Operator),
+            (" ", Category::Whitespace),
+            ("`a\\n\"b\"\nc`", Category::String),
+            (" ", Category::Whitespace),
+            ("+", Category::Operator),
+            (" ", Category::Whitespace),
+            ("\"\\\"d\"", Category::String),
+        ];
+
+        assert_eq!(tokens.len(), expected_tokens.len());
+        for (index, token) in tokens.iter().enumerate() {
+            assert_eq!(token.lexeme, expected_tokens[index].0);
+            assert_eq!(token.category, expected_tokens[index].1);
+        }
+    }
+
+    #[test]
+    fn it_can_handle_runes() {
+        let tokens = lex("r = '\\'' + 'é' // x");
+        let expected_tokens = [
+            ("r", Category::Identifier),
+            (" ", Category::Whitespace),
+            ("=", Category::Operator),
+            (" ", Category::Whitespace),
+            ("'\\''", Category::String),
+            (" ", Category::Whitespace),
+            ("+", Category::Operator),
+            (" ", Category::Whitespace),
+            ("'é'", Category::String),
+            (" ", Category::Whitespace),
+            ("// x", Category::Comment),
+        ];
+
+        assert_eq!(tokens.len(), expected_tokens.len());
+        for (index, token) in tokens.iter().enumerate() {
+            assert_eq!(token.lexeme, expected_tokens[index].0);
+            assert_eq!(token.category, expected_tokens[index].1);
+        }
+    }
+
+    #[test]
+    fn it_can_distinguish_keywords_from_identifiers() {
+        let tokens = lex("func gopher(ch chan int) { go f(0x1F, 2.5i, nil, true) }");
+        let expected_tokens = [
+            ("func", Category::Keyword),
+            (" ", Category::Whitespace),
+            ("gopher", Category::Identifier),
+            ("(", Category::Parenthesis),
+            ("ch", Category::Identifier),
+            (" ", Category::Whitespace),
+            ("chan", Category::Keyword),
+            (" ", Category::Whitespace),
+            ("int", Category::Identifier),
+            (")", Category::Parenthesis),
+            (" ", Category::Whitespace),
+            ("{", Category::Brace),
+            (" ", Category::Whitespace),
+            ("go", Category::Keyword),
+            (" ", Category::Whitespace),
+            ("f", Category::Identifier),
+            ("(", Category::Parenthesis),
+            ("0x1F", Category::Integer),
+            (",", Category::Punctuation),
+            (" ", Category::Whitespace),
+            ("2.5i", Category::Float),
+            (",", Category::Punctuation),
+            (" ", Category::Whitespace),
+            ("nil", Category::Keyword),
+            (",", Category::Punctuation),
+            (" ", Category::Whitespace),
+            ("true", Category::Boolean),
+            (")", Category::Parenthesis),
+            (" ", Category::Whitespace),
+            ("}", Category::Brace),
+        ];
+
+        assert_eq!(tokens.len(), expected_tokens.len());
+        for (index, token) in tokens.iter().enumerate() {
+            assert_eq!(token.lexeme, expected_tokens[index].0);
+            assert_eq!(token.category, expected_tokens[index].1);
+        }
+    }
+}
@@ -0,0 +1,125 @@
+use tokenizer::new;
+use token::Token;
+use token::Category;
+use super::lex_lines;
+
+fn line(data: &str) -> Vec<Token> {
+    let mut lexer = new(data);
+    lexer.skip_whitespace();
+
+    match lexer.current_char() {
+        Some('[') => {
+            lexer.advance_to(']');
+            lexer.accept("]");
+            lexer.tokenize(Category::Heading);
+            lexer.skip_whitespace();
+            lexer.tokenize_remaining(Category::Text);
+        },
+        Some(';') | Some('#') => lexer.tokenize_remaining(Category::Comment),
+        Some(_) => {
+            let remaining = lexer.remaining();
+
+            match remaining.find(['=', ':']) {
+                Some(offset) => {
+                    // Keys can contain spaces, but not trailing ones.
+                    let key_length = remaining[..offset].trim_end().chars().count();
+                    lexer.tokenize_next(key_length, Category::Identifier);
+                    lexer.skip_whitespace();
+                    lexer.tokenize_next(1, Category::AssignmentOperator);
+                    lexer.skip_whitespace();
+                    lexer.tokenize_remaining(Category::String);
+                },
+                None => lexer.tokenize_remaining(Category::Text),
+            }
+        },
+        None => (),
+    }
+
+    lexer.into_tokens()
+}
+
+/// Lexes the given INI or properties data, categorizing section headers,
+/// keys, assignment operators and values, and comments. Values are
+/// taken verbatim up to the end of the line.
+///
+/// # Examples
+///
+/// ```
+/// use luthor::lexers::ini;
+/// use luthor::token::Category;
+///
+/// let tokens = ini::lex("[core]\nname=luthor");
+/// assert_eq!(tokens[0].category, Category::Heading);
+/// assert_eq!(tokens[4].lexeme, "luthor");
+/// assert_eq!(tokens[4].category, Category::String);
+/// ```
+pub fn lex(data: &str) -> Vec<Token> {
+    lex_lines(data, line)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::lex;
+    use token::Category;
+
+    #[test]
+    fn it_can_handle_section_headers() {
+        let tokens = lex("[user settings] \n  [core]");
+        let expected_tokens = [
+            ("[user settings]", Category::Heading),
+            (" ", Category::Whitespace),
+            ("\n", Category::Whitespace),
+            ("  ", Category::Whitespace),
+            ("[core]", Category::Heading),
+        ];
+
+        assert_eq!(tokens.len(), expected_tokens.len());
+        for (index, token) in tokens.iter().enumerate() {
+            assert_eq!(token.lexeme, expected_tokens[index].0);
+            assert_eq!(token.category, expected_tokens[index].1);
+        }
+    }
+
+    #[test]
+    fn it_takes_values_verbatim() {
+        let tokens = lex("user name = a=b ; c\nurl: http://x");
+        let expected_tokens = [
+            ("user name", Category::Identifier),
+            (" ", Category::Whitespace),
+            ("=", Category::AssignmentOperator),
+            (" ", Category::Whitespace),
+            ("a=b ; c", Category::String),
+            ("\n", Category::Whitespace),
+            ("url", Category::Identifier),
+            (":", Category::AssignmentOperator),
+            (" ", Category::Whitespace),
+            ("http://x", Category::String),
+        ];
+
+        assert_eq!(tokens.len(), expected_tokens.len());
+        for (index, token) in tokens.iter().enumerate() {
+            assert_eq!(token.lexeme, expected_tokens[index].0);
+            assert_eq!(token.category, expected_tokens[index].1);
+        }
+    }
+
+    #[test]
+    fn it_can_handle_comments() {
+        let tokens = lex("; first = 1\n\n  # second\nflag");
+        let expected_tokens = [
+            ("; first = 1", Category::Comment),
+            ("\n", Category::Whitespace),
+            ("\n", Category::Whitespace),
+            ("  ", Category::Whitespace),
+            ("# second", Category::Comment),
+            ("\n", Category::Whitespace),
+            ("flag", Category::Text),
+        ];
+
+        assert_eq!(tokens.len(), expected_tokens.len());
+        for (index, token) in tokens.iter().enumerate() {
+            assert_eq!(token.lexeme, expected_tokens[index].0);
+            assert_eq!(token.category, expected_tokens[index].1);
+        }
+    }
+}
@@ -0,0 +1,359 @@
+use tokenizer::new;
+use tokenizer::Tokenizer;
+use tokenizer::StateFunction;
+use token::Token;
+use token::Category;
+
+const KEYWORDS: [&str; 40] = [
+    "async", "await", "break", "case", "catch", "class", "const", "continue",
+    "debugger", "default", "delete", "do", "else", "export", "extends",
+    "finally", "for", "function", "if", "import", "in", "instanceof", "let",
+    "new", "null", "of", "return", "static", "super", "switch", "this",
+    "throw", "try", "typeof", "undefined", "var", "void", "while", "with",
+    "yield",
+];
+
+// Ordered so that longer operators are matched before their prefixes.
+const OPERATORS: [&str; 49] = [
+    ">>>=", "...", "===", "!==", "**=", "<<=", ">>=", ">>>", "&&=", "||=",
+    "??=", "=>", "==", "!=", "<=", ">=", "&&", "||", "??", "?.", "++", "--",
+    "+=", "-=", "*=", "/=", "%=", "&=", "|=", "^=", "**", "<<", ">>", "+",
+    "-", "*", "/", "%", "&", "|", "^", "!", "~", "<", ">", "=", "?", ":", ".",
+];
+
+fn is_identifier_start(c: char) -> bool {
+    c.is_alphabetic() || c == '_' || c == '$'
+}
+
+fn is_identifier_char(c: char) -> bool {
+    c.is_alphanumeric() || c == '_' || c == '$'
+}
+
+// Creates a state that lexes code. Each element of the brace stack is
+// an open template literal interpolation, tracking the depth of the
+// braces within it, so that its closing brace can be identified.
+fn code(braces: Vec<usize>) -> StateFunction {
+    StateFunction::new(move |lexer: &mut Tokenizer| {
+        let c = lexer.current_char()?;
+        let mut braces = braces.clone();
+
+        match c {
+            '{' => {
+                if let Some(depth) = braces.last_mut() {
+                    *depth += 1;
+                }
+                lexer.tokenize_next(1, Category::Brace);
+            },
+            '}' => {
+                match braces.last_mut() {
+                    Some(0) => {
+                        braces.pop();
+                        lexer.tokenize_next(1, Category::Punctuation);
+                        return Some(template(braces));
+                    },
+                    Some(depth) => *depth -= 1,
+                    None => (),
+                }
+                lexer.tokenize_next(1, Category::Brace);
+            },
+            '[' | ']' => lexer.tokenize_next(1, Category::Bracket),
+            '(' | ')' => lexer.tokenize_next(1, Category::Parenthesis),
+            ';' | ',' => lexer.tokenize_next(1, Category::Punctuation),
+            '"' | '\'' => string(lexer),
+            '`' => {
+                lexer.advance();
+                return Some(template(braces));
+            },
+            _ => {
+                if c.is_whitespace() {
+                    lexer.skip_whitespace();
+                } else if lexer.starts_with("//") {
+                    lexer.consume_until(|c| c == '\n');
+                    lexer.tokenize(Category::Comment);
+                } else if lexer.starts_with("/*") {
+                    lexer.advance_by(2);
+                    while lexer.has_more_data() && !lexer.starts_with("*/") {
+                        lexer.advance();
+                    }
+                    lexer.advance_by(2);
+                    lexer.tokenize(Category::Comment);
+                } else if c == '/' && regex_allowed(lexer) {
+                    regex(lexer);
+                } else if c.is_ascii_digit() ||
+                    (c == '.' && lexer.peek_char().is_some_and(|c| c.is_ascii_digit())) {
+                    number(lexer);
+                } else if is_identifier_start(c) {
+                    lexer.consume_while(is_identifier_char);
+                    match lexer.current_lexeme() {
+                        "true" | "false" => lexer.tokenize(Category::Boolean),
+                        lexeme if KEYWORDS.contains(&lexeme) => lexer.tokenize(Category::Keyword),
+                        _ => lexer.tokenize(Category::Identifier),
+                    }
+                } else if !lexer.tokenize_match(&OPERATORS, Category::Operator) {
+                    lexer.tokenize_next(1, Category::Text);
+                }
+            }
+        }
+
+        Some(code(braces))
+    })
+}
+
+// Creates a state that lexes the literal portion of a template literal,
+// up to its closing backtick or the start of an interpolated expression.
+fn template(braces: Vec<usize>) -> StateFunction {
+    StateFunction::new(move |lexer: &mut Tokenizer| {
+        let mut braces = braces.clone();
+
+        loop {
+            match lexer.current_char() {
+                Some('`') => {
+                    lexer.advance();
+                    lexer.tokenize(Category::String);
+                    return Some(code(braces));
+                },
+                Some('\\') => lexer.advance_by(2),
+                Some('$') if lexer.peek_char() == Some('{') => {
+                    lexer.tokenize(Category::String);
+                    lexer.tokenize_next(2, Category::Punctuation);
+                    braces.push(0);
+                    return Some(code(braces));
+                },
+                Some(_) => lexer.advance(),
+                None => {
+                    lexer.tokenize(Category::String);
+                    return None;
+                },
+            }
+        }
+    })
+}
+
+// Determines whether or not a slash at the current position starts a
+// regular expression literal, rather than being a division operator,
+// based on the last significant token: divisions follow values.
+// last_token can't be used here, since whitespace and comments
+// between a value and the slash have to be skipped.
+fn regex_allowed(lexer: &Tokenizer) -> bool {
+    let previous_token = lexer.tokens_ref().iter().rev()
+        .find(|token| token.category != Category::Whitespace && token.category != Category::Comment);
+
+    match previous_token {
+        Some(token) => match token.category {
+            Category::Identifier | Category::Integer | Category::Float |
+                Category::String | Category::Regex | Category::Boolean => false,
+            Category::Keyword => token.lexeme != "this" && token.lexeme != "super",
+            Category::Parenthesis | Category::Bracket | Category::Brace => {
+                token.lexeme == "(" || token.lexeme == "[" || token.lexeme == "{"
+            },
+            Category::Operator => token.lexeme != "++" && token.lexeme != "--",
+            _ => true,
+        },
+        None => true,
+    }
+}
+
+fn regex(lexer: &mut Tokenizer) {
+    lexer.advance();
+
+    // Slashes within character classes don't end the expression.
+    let mut class = false;
+    while let Some(c) = lexer.current_char() {
+        if c == '\n' {
+            break;
+        }
+        lexer.advance();
+        match c {
+            '\\' => lexer.advance(),
+            '[' => class = true,
+            ']' => class = false,
+            '/' if !class => {
+                lexer.consume_while(is_identifier_char);
+                break;
+            },
+            _ => (),
+        }
+    }
+    lexer.tokenize(Category::Regex);
+}
+
+fn string(lexer: &mut Tokenizer) {
+    let quote = lexer.current_char().unwrap();
+    lexer.advance();
+
+    while let Some(c) = lexer.current_char() {
+        if c == '\n' {
+            break;
+        }
+        lexer.advance();
+        if c == quote {
+            break;
+        } else if c == '\\' {
+            lexer.advance();
+        }
+    }
+    lexer.tokenize(Category::String);
+}
+
+fn number(lexer: &mut Tokenizer) {
+    let mut category = Category::Integer;
+
+    if lexer.current_char() == Some('0') && lexer.peek_char().is_some_and(|c| "xXoObB".contains(c)) {
+        lexer.advance_by(2);
+        lexer.consume_while(|c| c.is_ascii_hexdigit() || c == '_');
+    } else {
+        lexer.consume_while(|c| c.is_ascii_digit() || c == '_');
+        if lexer.current_char() == Some('.') {
+            lexer.advance();
+            lexer.consume_while(|c| c.is_ascii_digit() || c == '_');
+            category = Category::Float;
+        }
+        if lexer.current_char().is_some_and(|c| c == 'e' || c == 'E') {
+            let exponent_start = match lexer.peek_char() {
+                Some('+') | Some('-') => 2,
+                _ => 1,
+            };
+            if lexer.peek(exponent_start).is_some_and(|c| c.is_ascii_digit()) {
+                lexer.advance_by(exponent_start);
+                lexer.consume_while(|c| c.is_ascii_digit() || c == '_');
+                category = Category::Float;
+            }
+        }
+    }
+
+    // BigInt literals are suffixed with an n.
+    lexer.accept("n");
+    lexer.tokenize(category);
+}
+
+/// Lexes the given JavaScript source, categorizing keywords, identifiers,
+/// numbers, strings, template literals, regular expressions, comments and
+/// operators. Slashes are categorized as regular expressions rather than
+/// division operators when they follow anything other than a value, and
+/// the expressions interpolated into template literals are lexed as code.
+///
+/// # Examples
+///
+/// ```
+/// use luthor::lexers::javascript;
+/// use luthor::token::Category;
+///
+/// let tokens = javascript::lex("x = /ab+c/g");
+/// assert_eq!(tokens[4].lexeme, "/ab+c/g");
+/// assert_eq!(tokens[4].category, Category::Regex);
+/// ```
+pub fn lex(data: &str) -> Vec<Token> {
+    let mut lexer = new(data);
+    lexer.run(code(Vec::new()));
+    lexer.into_tokens()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::lex;
+    use token::Category;
+
+    #[test]
+    fn it_can_distinguish_division_from_regexes() {
+        let tokens = lex("a / b; return /ab[/]c/i.test(s) /* ok */ / 2");
+        let expected_tokens = [
+            ("a", Category::Identifier),
+            (" ", Category::Whitespace),
+            ("/", Category::Operator),
+            (" ", Category::Whitespace),
+            ("b", Category::Identifier),
+            (";", Category::Punctuation),
+            (" ", Category::Whitespace),
+            ("return", Category::Keyword),
+            (" ", Category::Whitespace),
+            ("/ab[/]c/i", Category::Regex),
+            (".", Category::Operator),
+            ("test", Category::Identifier),
+            ("(", Category::Parenthesis),
+            ("s", Category::Identifier),
+            (")", Category::Parenthesis),
+            (" ", Category::Whitespace),
+            ("/* ok */", Category::Comment),
+            (" ", Category::Whitespace),
+            ("/", Category::Operator),
+            (" ", Category::Whitespace),
+            ("2", Category::Integer),
+        ];
+
+        assert_eq!(tokens.len(), expected_tokens.len());
+        for (index, token) in tokens.iter().enumerate() {
+            assert_eq!(token.lexeme, expected_tokens[index].0);
+            assert_eq!(token.category, expected_tokens[index].1);
+        }
+    }
+
+    #[test]
+    fn it_can_handle_template_literal_interpolation() {
+        let tokens = lex("`a ${b + {c: 1}.c} \\${d} ${`${e}`}`");
+        let expected_tokens = [
+            ("`a ", Category::String),
+            ("${", Category::Punctuation),
+            ("b", Category::Identifier),
+            (" ", Category::Whitespace),
+            ("+", Category::Operator),
+            (" ", Category::Whitespace),
+            ("{", Category::Brace),
+            ("c", Category::Identifier),
+            (":", Category::Operator),
+            (" ", Category::Whitespace),
+            ("1", Category::Integer),
+            ("}", Category::Brace),
+            (".", Category::Operator),
+            ("c", Category::Identifier),
+            ("}", Category::Punctuation),
+            (" \\${d} ", Category::String),
+            ("${", Category::Punctuation),
+            ("`", Category::String),
+            ("${", Category::Punctuation),
+            ("e", Category::Identifier),
+            ("}", Category::Punctuation),
+            ("`", Category::String),
+            ("}", Category::Punctuation),
+            ("`", Category::String),
+        ];
+
+        assert_eq!(tokens.len(), expected_tokens.len());
+        for (index, token) in tokens.iter().enumerate() {
+            assert_eq!(token.lexeme, expected_tokens[index].0);
+            assert_eq!(token.category, expected_tokens[index].1);
+        }
+    }
+
+    #[test]
+    fn it_can_handle_keywords_strings_and_numbers() {
+        let tokens = lex("const n = 0xFFn + .5e-3; let s = 'it\\'s'");
+        let expected_tokens = [
+            ("const", Category::Keyword),
+            (" ", Category::Whitespace),
+            ("n", Category::Identifier),
+            (" ", Category::Whitespace),
+            ("=", Category::Operator),
+            (" ", Category::Whitespace),
+            ("0xFFn", Category::Integer),
+            (" ", Category::Whitespace),
+            ("+", Category::Operator),
+            (" ", Category::Whitespace),
+            (".5e-3", Category::Float),
+            (";", Category::Punctuation),
+            (" ", Category::Whitespace),
+            ("let", Category::Keyword),
+            (" ", Category::Whitespace),
+            ("s", Category::Identifier),
+            (" ", Category::Whitespace),
+            ("=", Category::Operator),
+            (" ", Category::Whitespace),
+            ("'it\\'s'", Category::String),
+        ];
+
+        assert_eq!(tokens.len(), expected_tokens.len());
+        for (index, token) in tokens.iter().enumerate() {
+            assert_eq!(token.lexeme, expected_tokens[index].0);
+            assert_eq!(token.category, expected_tokens[index].1);
+        }
+    }
+}
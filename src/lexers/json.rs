@@ -4,6 +4,12 @@ use tokenizer::StateFunction;
 use token::Token;
 use token::Category;
 
+const DIGITS: &str = "0123456789";
+
+fn is_digit(c: char) -> bool {
+    c.is_ascii_digit()
+}
+
 fn initial_state(lexer: &mut Tokenizer) -> Option<StateFunction> {
     match lexer.current_char() {
         Some(c) => {
@@ -14,19 +20,22 @@ fn initial_state(lexer: &mut Tokenizer) -> Option<StateFunction> {
                 '[' => {
                     lexer.tokenize_next(1, Category::Bracket);
                 },
-                ' ' | '\n' => {
+                ' ' | '\t' | '\r' | '\n' => {
                     lexer.tokenize(Category::Text);
                     lexer.advance();
-                    return Some(StateFunction(whitespace));
+                    return Some(StateFunction::new(whitespace));
                 },
                 '"' => {
                     lexer.tokenize(Category::Text);
                     lexer.advance();
-                    return Some(StateFunction(inside_string));
+                    return Some(StateFunction::new(inside_string));
                 },
                 ':' => {
                     lexer.tokenize_next(1, Category::AssignmentOperator);
                 },
+                ',' => {
+                    lexer.tokenize_next(1, Category::Punctuation);
+                },
                 '}' => {
                     lexer.tokenize_next(1, Category::Brace);
                 },
@@ -35,15 +44,15 @@ fn initial_state(lexer: &mut Tokenizer) -> Option<StateFunction> {
                 },
                 _ => {
                     if lexer.token_position == lexer.token_start {
-                        let remaining_data = lexer.data
-                            .slice_from(lexer.token_position).to_string();
-
-                        if remaining_data.starts_with("true") {
+                        if lexer.starts_with("true") {
                             lexer.tokenize_next(4, Category::Boolean);
-                        } else if remaining_data.starts_with("false") {
+                        } else if lexer.starts_with("false") {
                             lexer.tokenize_next(5, Category::Boolean);
-                        } else if remaining_data.starts_with("null") {
+                        } else if lexer.starts_with("null") {
                             lexer.tokenize_next(4, Category::Keyword);
+                        } else if is_digit(c) ||
+                            (c == '-' && lexer.peek_char().is_some_and(is_digit)) {
+                            return Some(StateFunction::new(number));
                         } else {
                             lexer.advance();
                         }
@@ -53,7 +62,7 @@ fn initial_state(lexer: &mut Tokenizer) -> Option<StateFunction> {
                 }
             }
 
-            Some(StateFunction(initial_state))
+            Some(StateFunction::new(initial_state))
         }
 
         None => {
@@ -70,16 +79,16 @@ fn inside_string(lexer: &mut Tokenizer) -> Option<StateFunction> {
                 '"' => {
                     lexer.advance();
                     lexer.tokenize(Category::String);
-                    Some(StateFunction(initial_state))
+                    Some(StateFunction::new(initial_state))
                 },
                 '\\' => {
                     lexer.advance();
                     lexer.advance();
-                    Some(StateFunction(inside_string))
+                    Some(StateFunction::new(inside_string))
                 }
                 _ => {
                     lexer.advance();
-                    Some(StateFunction(inside_string))
+                    Some(StateFunction::new(inside_string))
                 }
             }
         }
@@ -91,17 +100,45 @@ fn inside_string(lexer: &mut Tokenizer) -> Option<StateFunction> {
     }
 }
 
+fn number(lexer: &mut Tokenizer) -> Option<StateFunction> {
+    let mut category = Category::Integer;
+    lexer.accept("-");
+    lexer.accept_run(DIGITS);
+
+    // Fractions and exponents are only part of the number
+    // if they're followed by at least one digit.
+    if lexer.current_char() == Some('.') && lexer.peek_char().is_some_and(is_digit) {
+        lexer.advance();
+        lexer.accept_run(DIGITS);
+        category = Category::Float;
+    }
+    if lexer.current_char().is_some_and(|c| c == 'e' || c == 'E') {
+        let exponent_start = match lexer.peek_char() {
+            Some('+') | Some('-') => 2,
+            _ => 1,
+        };
+        if lexer.peek(exponent_start).is_some_and(is_digit) {
+            lexer.advance_by(exponent_start);
+            lexer.accept_run(DIGITS);
+            category = Category::Float;
+        }
+    }
+
+    lexer.tokenize(category);
+    Some(StateFunction::new(initial_state))
+}
+
 fn whitespace(lexer: &mut Tokenizer) -> Option<StateFunction> {
     match lexer.current_char() {
         Some(c) => {
             match c {
-                ' ' | '\n' => {
+                ' ' | '\t' | '\r' | '\n' => {
                     lexer.advance();
-                    Some(StateFunction(whitespace))
+                    Some(StateFunction::new(whitespace))
                 },
                 _ => {
                     lexer.tokenize(Category::Whitespace);
-                    Some(StateFunction(initial_state))
+                    Some(StateFunction::new(initial_state))
                 }
             }
         }
@@ -113,103 +150,188 @@ fn whitespace(lexer: &mut Tokenizer) -> Option<StateFunction> {
     }
 }
 
+/// Lexes the given JSON data, categorizing strings, numbers,
+/// booleans, null, structural characters and whitespace.
+/// Anything else is categorized as Category::Text.
+///
+/// # Examples
+///
+/// ```
+/// use luthor::lexers::json;
+/// use luthor::token::Category;
+///
+/// let tokens = json::lex("[-1.5e3]");
+/// assert_eq!(tokens[1].lexeme, "-1.5e3");
+/// assert_eq!(tokens[1].category, Category::Float);
+/// ```
 pub fn lex(data: &str) -> Vec<Token> {
     let mut lexer = new(data);
-    let mut state_function = StateFunction(initial_state);
-    loop {
-        let StateFunction(actual_function) = state_function;
-        match actual_function(&mut lexer) {
-            Some(f) => state_function = f,
-            None => return lexer.tokens(),
-        }
-    }
+    lexer.run(StateFunction::new(initial_state));
+    lexer.into_tokens()
 }
 
+#[cfg(test)]
 mod tests {
     use super::lex;
-    use token::Token;
     use token::Category;
-    use std::old_io::{File, Open, Read};
+    use std::fs::File;
+    use std::io::Read;
 
     #[test]
     fn it_works() {
-        let data = File::open_mode(&Path::new("test_data/data.json"), Open, Read)
-            .unwrap().read_to_string().unwrap();
+        let mut data = String::new();
+        File::open("test_data/data.json").unwrap().read_to_string(&mut data).unwrap();
         let tokens = lex(&data);
-        let expected_tokens = vec![
-            Token{ lexeme: "{".to_string(), category: Category::Brace },
-            Token{ lexeme: "\n  ".to_string(), category: Category::Whitespace },
-            Token{ lexeme: "\"key\"".to_string(), category: Category::String },
-            Token{ lexeme: ":".to_string(), category: Category::AssignmentOperator },
-            Token{ lexeme: " ".to_string(), category: Category::Whitespace },
-            Token{ lexeme: "\"4032\"".to_string(), category: Category::String },
-            Token{ lexeme: ",".to_string(), category: Category::Text },
-            Token{ lexeme: "\n  ".to_string(), category: Category::Whitespace },
-            Token{ lexeme: "'single'".to_string(), category: Category::Text },
-            Token{ lexeme: ":".to_string(), category: Category::AssignmentOperator },
-            Token{ lexeme: " ".to_string(), category: Category::Whitespace },
-            Token{ lexeme: "'quotes\\'',".to_string(), category: Category::Text },
-            Token{ lexeme: "\n  ".to_string(), category: Category::Whitespace },
-            Token{ lexeme: "\"literals\"".to_string(), category: Category::String },
-            Token{ lexeme: ":".to_string(), category: Category::AssignmentOperator },
-            Token{ lexeme: " ".to_string(), category: Category::Whitespace },
-            Token{ lexeme: "[".to_string(), category: Category::Bracket },
-            Token{ lexeme: "\n    ".to_string(), category: Category::Whitespace },
-            Token{ lexeme: "true".to_string(), category: Category::Boolean },
-            Token{ lexeme: ",".to_string(), category: Category::Text },
-            Token{ lexeme: "\n    ".to_string(), category: Category::Whitespace },
-            Token{ lexeme: "false".to_string(), category: Category::Boolean },
-            Token{ lexeme: ",".to_string(), category: Category::Text },
-            Token{ lexeme: "\n    ".to_string(), category: Category::Whitespace },
-            Token{ lexeme: "null".to_string(), category: Category::Keyword },
-            Token{ lexeme: "\n  ".to_string(), category: Category::Whitespace },
-            Token{ lexeme: "]".to_string(), category: Category::Bracket },
-            Token{ lexeme: "\n".to_string(), category: Category::Whitespace },
-            Token{ lexeme: "}".to_string(), category: Category::Brace },
-            Token{ lexeme: "\n".to_string(), category: Category::Whitespace },
+        let expected_tokens = [
+            ("{", Category::Brace),
+            ("\n  ", Category::Whitespace),
+            ("\"key\"", Category::String),
+            (":", Category::AssignmentOperator),
+            (" ", Category::Whitespace),
+            ("\"4032\"", Category::String),
+            (",", Category::Punctuation),
+            ("\n  ", Category::Whitespace),
+            ("'single'", Category::Text),
+            (":", Category::AssignmentOperator),
+            (" ", Category::Whitespace),
+            ("'quotes\\''", Category::Text),
+            (",", Category::Punctuation),
+            ("\n  ", Category::Whitespace),
+            ("\"literals\"", Category::String),
+            (":", Category::AssignmentOperator),
+            (" ", Category::Whitespace),
+            ("[", Category::Bracket),
+            ("\n    ", Category::Whitespace),
+            ("true", Category::Boolean),
+            (",", Category::Punctuation),
+            ("\n    ", Category::Whitespace),
+            ("false", Category::Boolean),
+            (",", Category::Punctuation),
+            ("\n    ", Category::Whitespace),
+            ("null", Category::Keyword),
+            ("\n  ", Category::Whitespace),
+            ("]", Category::Bracket),
+            ("\n", Category::Whitespace),
+            ("}", Category::Brace),
+            ("\n", Category::Whitespace),
         ];
 
         for (index, token) in tokens.iter().enumerate() {
-            assert_eq!(*token, expected_tokens[index]);
+            assert_eq!(token.lexeme, expected_tokens[index].0);
+            assert_eq!(token.category, expected_tokens[index].1);
         }
     }
 
     #[test]
     fn it_can_handle_garbage() {
         let tokens = lex("} adwyx123&*_ ");
-        let expected_tokens = vec![
-            Token{ lexeme: "}".to_string(), category: Category::Brace },
-            Token{ lexeme: " ".to_string(), category: Category::Whitespace },
-            Token{ lexeme: "adwyx123&*_".to_string(), category: Category::Text },
-            Token{ lexeme: " ".to_string(), category: Category::Whitespace },
+        let expected_tokens = [
+            ("}", Category::Brace),
+            (" ", Category::Whitespace),
+            ("adwyx123&*_", Category::Text),
+            (" ", Category::Whitespace),
         ];
 
         for (index, token) in tokens.iter().enumerate() {
-            assert_eq!(*token, expected_tokens[index]);
+            assert_eq!(token.lexeme, expected_tokens[index].0);
+            assert_eq!(token.category, expected_tokens[index].1);
         }
     }
 
     #[test]
     fn it_can_handle_open_strings() {
         let tokens = lex("\"open!");
-        let expected_tokens = vec![
-            Token{ lexeme: "\"open!".to_string(), category: Category::String },
+        let expected_tokens = [
+            ("\"open!", Category::String),
         ];
 
         for (index, token) in tokens.iter().enumerate() {
-            assert_eq!(*token, expected_tokens[index]);
+            assert_eq!(token.lexeme, expected_tokens[index].0);
+            assert_eq!(token.category, expected_tokens[index].1);
         }
     }
 
     #[test]
     fn it_can_handle_utf8_data() {
         let tokens = lex("différent");
-        let expected_tokens = vec![
-            Token{ lexeme: "différent".to_string(), category: Category::Text },
+        let expected_tokens = [
+            ("différent", Category::Text),
+        ];
+
+        for (index, token) in tokens.iter().enumerate() {
+            assert_eq!(token.lexeme, expected_tokens[index].0);
+            assert_eq!(token.category, expected_tokens[index].1);
+        }
+    }
+
+    #[test]
+    fn it_can_handle_nested_objects() {
+        let tokens = lex("{\"a\":{\"b\":[1,2.5]}}");
+        let expected_tokens = [
+            ("{", Category::Brace),
+            ("\"a\"", Category::String),
+            (":", Category::AssignmentOperator),
+            ("{", Category::Brace),
+            ("\"b\"", Category::String),
+            (":", Category::AssignmentOperator),
+            ("[", Category::Bracket),
+            ("1", Category::Integer),
+            (",", Category::Punctuation),
+            ("2.5", Category::Float),
+            ("]", Category::Bracket),
+            ("}", Category::Brace),
+            ("}", Category::Brace),
+        ];
+
+        assert_eq!(tokens.len(), expected_tokens.len());
+        for (index, token) in tokens.iter().enumerate() {
+            assert_eq!(token.lexeme, expected_tokens[index].0);
+            assert_eq!(token.category, expected_tokens[index].1);
+        }
+    }
+
+    #[test]
+    fn it_can_handle_escaped_quotes_in_strings() {
+        let tokens = lex("[\"say \\\"hi\\\"\", \"\\\\\"]");
+        let expected_tokens = [
+            ("[", Category::Bracket),
+            ("\"say \\\"hi\\\"\"", Category::String),
+            (",", Category::Punctuation),
+            (" ", Category::Whitespace),
+            ("\"\\\\\"", Category::String),
+            ("]", Category::Bracket),
+        ];
+
+        assert_eq!(tokens.len(), expected_tokens.len());
+        for (index, token) in tokens.iter().enumerate() {
+            assert_eq!(token.lexeme, expected_tokens[index].0);
+            assert_eq!(token.category, expected_tokens[index].1);
+        }
+    }
+
+    #[test]
+    fn it_can_handle_numbers() {
+        let tokens = lex("[-12, 6.02E+23, -1.5e-10, 1.]");
+        let expected_tokens = [
+            ("[", Category::Bracket),
+            ("-12", Category::Integer),
+            (",", Category::Punctuation),
+            (" ", Category::Whitespace),
+            ("6.02E+23", Category::Float),
+            (",", Category::Punctuation),
+            (" ", Category::Whitespace),
+            ("-1.5e-10", Category::Float),
+            (",", Category::Punctuation),
+            (" ", Category::Whitespace),
+            ("1", Category::Integer),
+            (".", Category::Text),
+            ("]", Category::Bracket),
         ];
 
+        assert_eq!(tokens.len(), expected_tokens.len());
         for (index, token) in tokens.iter().enumerate() {
-            assert_eq!(*token, expected_tokens[index]);
+            assert_eq!(token.lexeme, expected_tokens[index].0);
+            assert_eq!(token.category, expected_tokens[index].1);
         }
     }
 }
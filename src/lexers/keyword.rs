@@ -0,0 +1,164 @@
+use std::collections::HashSet;
+use tokenizer::new;
+use token::Token;
+use token::Category;
+
+/// The KeywordLexer type is a configurable lexer that categorizes
+/// a set of words as keywords and everything else as text, for
+/// cases where a full language lexer would be overkill.
+#[derive(Debug, Clone, Default)]
+pub struct KeywordLexer {
+    keywords: HashSet<String>,
+    delimiters: String,
+}
+
+impl KeywordLexer {
+    /// Initializes a new lexer without any keywords or delimiters.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use luthor::lexers::keyword::KeywordLexer;
+    ///
+    /// let lexer = KeywordLexer::new();
+    /// ```
+    pub fn new() -> KeywordLexer {
+        KeywordLexer::default()
+    }
+
+    /// Adds the given words to the lexer's keyword set.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use luthor::lexers::keyword::KeywordLexer;
+    /// use luthor::token::Category;
+    ///
+    /// let lexer = KeywordLexer::new().keywords(&["let", "in"]);
+    /// assert_eq!(lexer.lex("let")[0].category, Category::Keyword);
+    /// ```
+    pub fn keywords(mut self, keywords: &[&str]) -> KeywordLexer {
+        self.keywords.extend(keywords.iter().map(|keyword| keyword.to_string()));
+        self
+    }
+
+    /// Adds the given characters to the lexer's delimiters, which end
+    /// words and are categorized as punctuation, one token apiece.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use luthor::lexers::keyword::KeywordLexer;
+    /// use luthor::token::Category;
+    ///
+    /// let lexer = KeywordLexer::new().keywords(&["end"]).delimiters(";");
+    /// let tokens = lexer.lex("end;");
+    /// assert_eq!(tokens[0].category, Category::Keyword);
+    /// assert_eq!(tokens[1].category, Category::Punctuation);
+    /// ```
+    pub fn delimiters(mut self, delimiters: &str) -> KeywordLexer {
+        self.delimiters.push_str(delimiters);
+        self
+    }
+
+    /// Lexes the given data, splitting it into words at whitespace
+    /// and delimiters. Words in the keyword set are categorized
+    /// as keywords, and all other words as text.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use luthor::lexers::keyword::KeywordLexer;
+    /// use luthor::token::Category;
+    ///
+    /// let lexer = KeywordLexer::new().keywords(&["if"]);
+    /// let tokens = lexer.lex("if luthor");
+    /// assert_eq!(tokens[2].lexeme, "luthor");
+    /// assert_eq!(tokens[2].category, Category::Text);
+    /// ```
+    pub fn lex(&self, data: &str) -> Vec<Token> {
+        self.lex_with_whitespace(data, char::is_whitespace)
+    }
+
+    // Lexes the given data, treating the characters matched
+    // by the given predicate as whitespace.
+    pub(crate) fn lex_with_whitespace<F>(&self, data: &str, is_whitespace: F) -> Vec<Token>
+        where F: Fn(char) -> bool {
+        let mut lexer = new(data);
+        let is_word_char = |c: char| !is_whitespace(c) && !self.delimiters.contains(c);
+
+        while let Some(c) = lexer.current_char() {
+            if is_whitespace(c) {
+                lexer.consume_while(&is_whitespace);
+                lexer.tokenize(Category::Whitespace);
+            } else if self.delimiters.contains(c) {
+                lexer.tokenize_next(1, Category::Punctuation);
+            } else {
+                lexer.consume_while(is_word_char);
+
+                if self.keywords.contains(lexer.current_lexeme()) {
+                    lexer.tokenize(Category::Keyword);
+                } else {
+                    lexer.tokenize(Category::Text);
+                }
+            }
+        }
+
+        lexer.into_tokens()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::KeywordLexer;
+    use token::Category;
+
+    #[test]
+    fn it_categorizes_custom_keywords() {
+        let lexer = KeywordLexer::new()
+            .keywords(&["select", "from"])
+            .keywords(&["where"])
+            .delimiters(",()");
+        let tokens = lexer.lex("select a,b from t where selected(a)");
+        let expected_tokens = [
+            ("select", Category::Keyword),
+            (" ", Category::Whitespace),
+            ("a", Category::Text),
+            (",", Category::Punctuation),
+            ("b", Category::Text),
+            (" ", Category::Whitespace),
+            ("from", Category::Keyword),
+            (" ", Category::Whitespace),
+            ("t", Category::Text),
+            (" ", Category::Whitespace),
+            ("where", Category::Keyword),
+            (" ", Category::Whitespace),
+            ("selected", Category::Text),
+            ("(", Category::Punctuation),
+            ("a", Category::Text),
+            (")", Category::Punctuation),
+        ];
+
+        assert_eq!(tokens.len(), expected_tokens.len());
+        for (index, token) in tokens.iter().enumerate() {
+            assert_eq!(token.lexeme, expected_tokens[index].0);
+            assert_eq!(token.category, expected_tokens[index].1);
+        }
+    }
+
+    #[test]
+    fn it_categorizes_everything_as_text_without_keywords() {
+        let tokens = KeywordLexer::new().lex("if x;y");
+        let expected_tokens = [
+            ("if", Category::Text),
+            (" ", Category::Whitespace),
+            ("x;y", Category::Text),
+        ];
+
+        assert_eq!(tokens.len(), expected_tokens.len());
+        for (index, token) in tokens.iter().enumerate() {
+            assert_eq!(token.lexeme, expected_tokens[index].0);
+            assert_eq!(token.category, expected_tokens[index].1);
+        }
+    }
+}
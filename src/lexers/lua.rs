@@ -0,0 +1,228 @@
+use tokenizer::new;
+use tokenizer::Tokenizer;
+use tokenizer::StateFunction;
+use token::Token;
+use token::Category;
+
+const KEYWORDS: [&str; 19] = [
+    "and", "break", "do", "else", "elseif", "end", "for", "function", "goto",
+    "if", "in", "local", "nil", "not", "or", "repeat", "return", "then",
+    "until",
+];
+
+// Ordered so that longer operators are matched before their prefixes.
+const OPERATORS: [&str; 25] = [
+    "...", "..", "==", "~=", "<=", ">=", "//", "<<", ">>", "::", "+", "-",
+    "*", "/", "%", "^", "#", "&", "~", "|", "<", ">", "=", ":", ".",
+];
+
+fn is_identifier_char(c: char) -> bool {
+    c.is_alphanumeric() || c == '_'
+}
+
+fn initial_state(lexer: &mut Tokenizer) -> Option<StateFunction> {
+    let c = lexer.current_char()?;
+
+    if c.is_whitespace() {
+        lexer.skip_whitespace();
+    } else if lexer.starts_with("--") {
+        lexer.advance_by(2);
+        if !long_bracket(lexer) {
+            lexer.consume_until(|c| c == '\n');
+        }
+        lexer.tokenize(Category::Comment);
+    } else if long_bracket(lexer) {
+        lexer.tokenize(Category::String);
+    } else if c == '"' || c == '\'' {
+        lexer.advance();
+        while let Some(current) = lexer.current_char() {
+            if current == '\n' {
+                break;
+            }
+            lexer.advance();
+            if current == c {
+                break;
+            } else if current == '\\' {
+                lexer.advance();
+            }
+        }
+        lexer.tokenize(Category::String);
+    } else if c.is_ascii_digit() ||
+        (c == '.' && lexer.peek_char().is_some_and(|c| c.is_ascii_digit())) {
+        number(lexer);
+    } else if c.is_alphabetic() || c == '_' {
+        lexer.consume_while(is_identifier_char);
+        match lexer.current_lexeme() {
+            "true" | "false" => lexer.tokenize(Category::Boolean),
+            lexeme if KEYWORDS.contains(&lexeme) => lexer.tokenize(Category::Keyword),
+            _ => lexer.tokenize(Category::Identifier),
+        }
+    } else {
+        match c {
+            '{' | '}' => lexer.tokenize_next(1, Category::Brace),
+            '[' | ']' => lexer.tokenize_next(1, Category::Bracket),
+            '(' | ')' => lexer.tokenize_next(1, Category::Parenthesis),
+            ';' | ',' => lexer.tokenize_next(1, Category::Punctuation),
+            _ => {
+                if !lexer.tokenize_match(&OPERATORS, Category::Operator) {
+                    lexer.tokenize_next(1, Category::Text);
+                }
+            }
+        }
+    }
+
+    Some(StateFunction::new(initial_state))
+}
+
+// Advances over a long bracket (e.g. [[ ... ]] or [==[ ... ]==]) if one starts
+// at the current position. Long brackets are only closed by a bracket of the
+// same level (i.e. with the same number of equals signs), or the end of data.
+fn long_bracket(lexer: &mut Tokenizer) -> bool {
+    if lexer.current_char() != Some('[') {
+        return false;
+    }
+
+    let mut level = 1;
+    while lexer.peek(level) == Some('=') {
+        level += 1;
+    }
+    if lexer.peek(level) != Some('[') {
+        return false;
+    }
+
+    let closing_bracket = format!("]{}]", "=".repeat(level - 1));
+    lexer.advance_by(level + 1);
+    while lexer.has_more_data() && !lexer.starts_with(&closing_bracket) {
+        lexer.advance();
+    }
+    lexer.advance_by(level + 1);
+
+    true
+}
+
+fn number(lexer: &mut Tokenizer) {
+    let mut category = Category::Integer;
+
+    if lexer.current_char() == Some('0') && lexer.peek_char().is_some_and(|c| c == 'x' || c == 'X') {
+        lexer.advance_by(2);
+        lexer.consume_while(|c| c.is_ascii_hexdigit());
+    } else {
+        lexer.consume_while(|c| c.is_ascii_digit());
+        if lexer.current_char() == Some('.') && lexer.peek_char() != Some('.') {
+            lexer.advance();
+            lexer.consume_while(|c| c.is_ascii_digit());
+            category = Category::Float;
+        }
+        if lexer.current_char().is_some_and(|c| c == 'e' || c == 'E') {
+            let exponent_start = match lexer.peek_char() {
+                Some('+') | Some('-') => 2,
+                _ => 1,
+            };
+            if lexer.peek(exponent_start).is_some_and(|c| c.is_ascii_digit()) {
+                lexer.advance_by(exponent_start);
+                lexer.consume_while(|c| c.is_ascii_digit());
+                category = Category::Float;
+            }
+        }
+    }
+    lexer.tokenize(category);
+}
+
+/// Lexes the given Lua source, categorizing keywords, identifiers, numbers,
+/// strings, comments and operators. Long brackets (e.g. `[==[ ... ]==]`)
+/// are lexed as strings, or as comments when prefixed with `--`.
+///
+/// # Examples
+///
+/// ```
+/// use luthor::lexers::lua;
+/// use luthor::token::Category;
+///
+/// let tokens = lua::lex("s = [[long]]");
+/// assert_eq!(tokens[4].lexeme, "[[long]]");
+/// assert_eq!(tokens[4].category, Category::String);
+/// ```
+pub fn lex(data: &str) -> Vec<Token> {
+    let mut lexer = new(data);
+    lexer.run(StateFunction::new(initial_state));
+    lexer.into_tokens()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::lex;
+    use token::Category;
+
+    #[test]
+    fn it_can_handle_long_strings() {
+        let tokens = lex("local s = [[a\n]=]b]] .. t[1]");
+        let expected_tokens = [
+            ("local", Category::Keyword),
+            (" ", Category::Whitespace),
+            ("s", Category::Identifier),
+            (" ", Category::Whitespace),
+            ("=", Category::Operator),
+            (" ", Category::Whitespace),
+            ("[[a\n]=]b]]", Category::String),
+            (" ", Category::Whitespace),
+            ("..", Category::Operator),
+            (" ", Category::Whitespace),
+            ("t", Category::Identifier),
+            ("[", Category::Bracket),
+            ("1", Category::Integer),
+            ("]", Category::Bracket),
+        ];
+
+        assert_eq!(tokens.len(), expected_tokens.len());
+        for (index, token) in tokens.iter().enumerate() {
+            assert_eq!(token.lexeme, expected_tokens[index].0);
+            assert_eq!(token.category, expected_tokens[index].1);
+        }
+    }
+
+    #[test]
+    fn it_only_closes_long_strings_at_the_same_level() {
+        let tokens = lex("s = [==[a]]b]=]c]==] 'd'");
+        let expected_tokens = [
+            ("s", Category::Identifier),
+            (" ", Category::Whitespace),
+            ("=", Category::Operator),
+            (" ", Category::Whitespace),
+            ("[==[a]]b]=]c]==]", Category::String),
+            (" ", Category::Whitespace),
+            ("'d'", Category::String),
+        ];
+
+        assert_eq!(tokens.len(), expected_tokens.len());
+        for (index, token) in tokens.iter().enumerate() {
+            assert_eq!(token.lexeme, expected_tokens[index].0);
+            assert_eq!(token.category, expected_tokens[index].1);
+        }
+    }
+
+    #[test]
+    fn it_can_handle_comments() {
+        let tokens = lex("--[=[ a\n]] ]=] x = 1.5 -- y\nreturn nil");
+        let expected_tokens = [
+            ("--[=[ a\n]] ]=]", Category::Comment),
+            (" ", Category::Whitespace),
+            ("x", Category::Identifier),
+            (" ", Category::Whitespace),
+            ("=", Category::Operator),
+            (" ", Category::Whitespace),
+            ("1.5", Category::Float),
+            (" ", Category::Whitespace),
+            ("-- y", Category::Comment),
+            ("\n", Category::Whitespace),
+            ("return", Category::Keyword),
+            (" ", Category::Whitespace),
+            ("nil", Category::Keyword),
+        ];
+
+        assert_eq!(tokens.len(), expected_tokens.len());
+        for (index, token) in tokens.iter().enumerate() {
+            assert_eq!(token.lexeme, expected_tokens[index].0);
+            assert_eq!(token.category, expected_tokens[index].1);
+        }
+    }
+}
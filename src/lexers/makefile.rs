@@ -0,0 +1,274 @@
+use tokenizer::new;
+use tokenizer::Tokenizer;
+use token::Token;
+use token::Category;
+use super::lex_lines;
+
+const DIRECTIVES: [&str; 16] = [
+    "include", "-include", "sinclude", "ifeq", "ifneq", "ifdef", "ifndef",
+    "else", "endif", "define", "endef", "export", "unexport", "override",
+    "private", "vpath",
+];
+
+// Ordered so that longer operators are matched before their prefixes.
+const ASSIGNMENT_OPERATORS: [&str; 6] = ["::=", ":=", "?=", "+=", "!=", "="];
+
+fn line(data: &str) -> Vec<Token> {
+    let mut lexer = new(data);
+
+    // Only lines indented with a tab are recipes; spaces aren't significant.
+    if lexer.current_char() == Some('\t') {
+        lexer.tokenize_char(Category::Whitespace);
+        recipe(&mut lexer);
+        return lexer.into_tokens();
+    }
+
+    lexer.skip_whitespace();
+    if lexer.tokenize_line_comment("#", Category::Comment) {
+        return lexer.into_tokens();
+    }
+
+    let checkpoint = lexer.checkpoint();
+    lexer.consume_while(|c| !c.is_whitespace());
+    if lexer.current_is_one_of(&DIRECTIVES) {
+        lexer.tokenize(Category::Keyword);
+        words(&mut lexer, Category::Text);
+        return lexer.into_tokens();
+    }
+    lexer.restore(checkpoint);
+
+    match separator(lexer.remaining()) {
+        Some((offset, true)) => {
+            let position = lexer.token_position + offset;
+            words_until(&mut lexer, position, Category::Variable);
+            lexer.tokenize_match(&ASSIGNMENT_OPERATORS, Category::AssignmentOperator);
+            words(&mut lexer, Category::Text);
+        },
+        Some((offset, false)) => {
+            let position = lexer.token_position + offset;
+            words_until(&mut lexer, position, Category::Identifier);
+            lexer.tokenize_match(&["::", ":"], Category::Punctuation);
+            prerequisites(&mut lexer);
+        },
+        None => words(&mut lexer, Category::Text),
+    }
+
+    lexer.into_tokens()
+}
+
+// Finds the character offset of the first assignment operator or rule
+// separator in the line, outside of any variable references, along with
+// whether or not it's an assignment operator.
+fn separator(line: &str) -> Option<(usize, bool)> {
+    let mut depth = 0;
+
+    for (index, (offset, c)) in line.char_indices().enumerate() {
+        match c {
+            '(' | '{' => depth += 1,
+            ')' | '}' => depth -= 1,
+            '#' if depth == 0 => return None,
+            ':' | '=' | '?' | '+' | '!' if depth == 0 => {
+                let rest = &line[offset..];
+                if ASSIGNMENT_OPERATORS.iter().any(|operator| rest.starts_with(operator)) {
+                    return Some((index, true));
+                } else if c == ':' {
+                    return Some((index, false));
+                }
+            },
+            _ => (),
+        }
+    }
+
+    None
+}
+
+// Lexes whitespace-separated words with the given category, along with
+// variable references and comments, until the given character position.
+fn words_until(lexer: &mut Tokenizer, position: usize, category: Category) {
+    let is_word_char = |c: char| !c.is_whitespace() && c != '$' && c != '#';
+
+    while lexer.token_position < position {
+        match lexer.current_char() {
+            Some('$') => variable_reference(lexer),
+            Some('#') => {
+                lexer.tokenize_line_comment("#", Category::Comment);
+            },
+            Some(c) if c.is_whitespace() => lexer.skip_whitespace(),
+            Some(_) => {
+                while lexer.token_position < position && lexer.current_char().is_some_and(is_word_char) {
+                    lexer.advance();
+                }
+                lexer.tokenize(category.clone());
+            },
+            None => break,
+        }
+    }
+}
+
+// Lexes the rest of the line as words with the given category.
+fn words(lexer: &mut Tokenizer, category: Category) {
+    let position = lexer.data_len();
+    words_until(lexer, position, category);
+}
+
+// Lexes a rule's prerequisites, which can be followed
+// by a recipe on the same line after a semicolon.
+fn prerequisites(lexer: &mut Tokenizer) {
+    let remaining = lexer.remaining();
+    match remaining.find(';') {
+        Some(offset) if !remaining[..offset].contains('#') => {
+            let position = lexer.token_position + remaining[..offset].chars().count();
+            words_until(lexer, position, Category::Text);
+            lexer.tokenize_char(Category::Punctuation);
+            recipe(lexer);
+        },
+        _ => words(lexer, Category::Text),
+    }
+}
+
+// Lexes a recipe, which is passed verbatim to the shell
+// once any variable references in it have been expanded.
+fn recipe(lexer: &mut Tokenizer) {
+    while lexer.has_more_data() {
+        lexer.consume_until(|c| c == '$');
+        lexer.tokenize(Category::Code);
+        if lexer.has_more_data() {
+            variable_reference(lexer);
+        }
+    }
+}
+
+fn variable_reference(lexer: &mut Tokenizer) {
+    lexer.tokenize(Category::Text);
+    lexer.advance();
+    match lexer.current_char() {
+        Some('(') => {
+            lexer.consume_balanced('(', ')');
+        },
+        Some('{') => {
+            lexer.consume_balanced('{', '}');
+        },
+        _ => lexer.advance(),
+    }
+    lexer.tokenize(Category::Variable);
+}
+
+/// Lexes the given Makefile, categorizing rule targets as identifiers,
+/// prerequisites as text, tab-indented recipe lines as code, assigned
+/// variables, assignment operators, variable references, directives and
+/// comments. Lines indented with spaces rather than a tab aren't recipes.
+///
+/// # Examples
+///
+/// ```
+/// use luthor::lexers::makefile;
+/// use luthor::token::Category;
+///
+/// let tokens = makefile::lex("all: luthor\n\tcargo build");
+/// assert_eq!(tokens[0].category, Category::Identifier);
+/// assert_eq!(tokens[3].category, Category::Text);
+/// assert_eq!(tokens[6].lexeme, "cargo build");
+/// assert_eq!(tokens[6].category, Category::Code);
+/// ```
+pub fn lex(data: &str) -> Vec<Token> {
+    lex_lines(data, line)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::lex;
+    use token::Category;
+
+    #[test]
+    fn it_can_handle_rules() {
+        let tokens = lex("build test:: src/main.rs | out # deps\n  not: a recipe");
+        let expected_tokens = [
+            ("build", Category::Identifier),
+            (" ", Category::Whitespace),
+            ("test", Category::Identifier),
+            ("::", Category::Punctuation),
+            (" ", Category::Whitespace),
+            ("src/main.rs", Category::Text),
+            (" ", Category::Whitespace),
+            ("|", Category::Text),
+            (" ", Category::Whitespace),
+            ("out", Category::Text),
+            (" ", Category::Whitespace),
+            ("# deps", Category::Comment),
+            ("\n", Category::Whitespace),
+            ("  ", Category::Whitespace),
+            ("not", Category::Identifier),
+            (":", Category::Punctuation),
+            (" ", Category::Whitespace),
+            ("a", Category::Text),
+            (" ", Category::Whitespace),
+            ("recipe", Category::Text),
+        ];
+
+        assert_eq!(tokens.len(), expected_tokens.len());
+        for (index, token) in tokens.iter().enumerate() {
+            assert_eq!(token.lexeme, expected_tokens[index].0);
+            assert_eq!(token.category, expected_tokens[index].1);
+        }
+    }
+
+    #[test]
+    fn it_can_handle_tab_indented_recipes() {
+        let tokens = lex("all: ; @echo $@\n\t-rm -rf $(OUT) # gone\n    ifdef X");
+        let expected_tokens = [
+            ("all", Category::Identifier),
+            (":", Category::Punctuation),
+            (" ", Category::Whitespace),
+            (";", Category::Punctuation),
+            (" @echo ", Category::Code),
+            ("$@", Category::Variable),
+            ("\n", Category::Whitespace),
+            ("\t", Category::Whitespace),
+            ("-rm -rf ", Category::Code),
+            ("$(OUT)", Category::Variable),
+            (" # gone", Category::Code),
+            ("\n", Category::Whitespace),
+            ("    ", Category::Whitespace),
+            ("ifdef", Category::Keyword),
+            (" ", Category::Whitespace),
+            ("X", Category::Text),
+        ];
+
+        assert_eq!(tokens.len(), expected_tokens.len());
+        for (index, token) in tokens.iter().enumerate() {
+            assert_eq!(token.lexeme, expected_tokens[index].0);
+            assert_eq!(token.category, expected_tokens[index].1);
+        }
+    }
+
+    #[test]
+    fn it_can_handle_assignments_and_variable_references() {
+        let tokens = lex("CC ?= gcc\nFLAGS:=$(CC) ${OPT:a=b} -O2\n$(OUT): $(SRC)");
+        let expected_tokens = [
+            ("CC", Category::Variable),
+            (" ", Category::Whitespace),
+            ("?=", Category::AssignmentOperator),
+            (" ", Category::Whitespace),
+            ("gcc", Category::Text),
+            ("\n", Category::Whitespace),
+            ("FLAGS", Category::Variable),
+            (":=", Category::AssignmentOperator),
+            ("$(CC)", Category::Variable),
+            (" ", Category::Whitespace),
+            ("${OPT:a=b}", Category::Variable),
+            (" ", Category::Whitespace),
+            ("-O2", Category::Text),
+            ("\n", Category::Whitespace),
+            ("$(OUT)", Category::Variable),
+            (":", Category::Punctuation),
+            (" ", Category::Whitespace),
+            ("$(SRC)", Category::Variable),
+        ];
+
+        assert_eq!(tokens.len(), expected_tokens.len());
+        for (index, token) in tokens.iter().enumerate() {
+            assert_eq!(token.lexeme, expected_tokens[index].0);
+            assert_eq!(token.category, expected_tokens[index].1);
+        }
+    }
+}
@@ -0,0 +1,293 @@
+use tokenizer::new;
+use tokenizer::Tokenizer;
+use tokenizer::StateFunction;
+use token::Token;
+use token::Category;
+
+// Returns the unprocessed data up to the end of the current line.
+fn rest_of_line(lexer: &Tokenizer) -> &str {
+    let remaining = lexer.remaining();
+    match remaining.find('\n') {
+        Some(index) => &remaining[..index],
+        None => remaining,
+    }
+}
+
+fn line_start(lexer: &mut Tokenizer) -> Option<StateFunction> {
+    lexer.consume_while(|c| c == ' ' || c == '\t');
+    lexer.tokenize(Category::Whitespace);
+
+    let line = rest_of_line(lexer);
+    let heading_level = line.chars().take_while(|&c| c == '#').count();
+    let list_marker_length = list_marker_length(line);
+
+    if line.starts_with("```") || line.starts_with("~~~") {
+        return Some(StateFunction::new(fenced_code));
+    } else if heading_level > 0 && heading_level <= 6 &&
+        line[heading_level..].chars().next().is_none_or(char::is_whitespace) {
+        lexer.consume_until(|c| c == '\n');
+        lexer.tokenize(Category::Heading);
+    } else if list_marker_length > 0 {
+        lexer.tokenize_next(list_marker_length, Category::ListMarker);
+    }
+
+    Some(StateFunction::new(inline))
+}
+
+// Returns the length of the list marker (e.g. "-", "*", "1.")
+// at the start of the line, or zero if there isn't one.
+fn list_marker_length(line: &str) -> usize {
+    let digits = line.chars().take_while(|c| c.is_ascii_digit()).count();
+    let length = if digits > 0 {
+        match line[digits..].chars().next() {
+            Some('.') | Some(')') => digits + 1,
+            _ => return 0,
+        }
+    } else {
+        match line.chars().next() {
+            Some('-') | Some('*') | Some('+') => 1,
+            _ => return 0,
+        }
+    };
+
+    if line[length..].starts_with(' ') || line[length..].starts_with('\t') {
+        length
+    } else {
+        0
+    }
+}
+
+fn fenced_code(lexer: &mut Tokenizer) -> Option<StateFunction> {
+    let fence = if lexer.starts_with("```") { "```" } else { "~~~" };
+    lexer.consume_until(|c| c == '\n');
+
+    while lexer.has_more_data() {
+        lexer.advance();
+        let closed = rest_of_line(lexer).trim_start().starts_with(fence);
+        lexer.consume_until(|c| c == '\n');
+        if closed {
+            break;
+        }
+    }
+    lexer.tokenize(Category::Code);
+
+    Some(StateFunction::new(inline))
+}
+
+fn inline(lexer: &mut Tokenizer) -> Option<StateFunction> {
+    match lexer.current_char() {
+        Some(c) => {
+            let length = match c {
+                '\n' => {
+                    lexer.tokenize(Category::Text);
+                    lexer.tokenize_next(1, Category::Whitespace);
+                    return Some(StateFunction::new(line_start));
+                },
+                '\\' => {
+                    // Escaped characters are always literal.
+                    lexer.advance();
+                    if lexer.current_char() != Some('\n') {
+                        lexer.advance();
+                    }
+                    return Some(StateFunction::new(inline));
+                },
+                '`' => code_span_length(lexer).map(|length| (length, Category::Code)),
+                '*' | '_' => emphasis_length(lexer).map(|length| (length, Category::Emphasis)),
+                '[' => link_length(lexer).map(|length| (length, Category::Link)),
+                _ => None,
+            };
+
+            match length {
+                Some((length, category)) => lexer.tokenize_next(length, category),
+                None => lexer.advance(),
+            }
+
+            Some(StateFunction::new(inline))
+        }
+
+        None => {
+            lexer.tokenize(Category::Text);
+            None
+        }
+    }
+}
+
+// The following functions determine the length of a well-formed span
+// starting at the current position. Unmatched delimiters have no span,
+// and are left to be categorized as text.
+
+fn code_span_length(lexer: &Tokenizer) -> Option<usize> {
+    let remaining = lexer.remaining();
+    let delimiter_length = remaining.chars().take_while(|&c| c == '`').count();
+    let delimiter = &remaining[..delimiter_length];
+
+    remaining[delimiter_length..].find(delimiter).map(|index| {
+        2 * delimiter_length + remaining[delimiter_length..delimiter_length + index].chars().count()
+    })
+}
+
+fn emphasis_length(lexer: &Tokenizer) -> Option<usize> {
+    let line = rest_of_line(lexer);
+    let marker = lexer.current_char().unwrap();
+    let delimiter_length = line.chars().take_while(|&c| c == marker).count();
+    let delimiter = &line[..delimiter_length];
+    let content = &line[delimiter_length..];
+
+    // Underscores within words, such as in snake_case, aren't emphasis.
+    let within_word = marker == '_' &&
+        lexer.current_lexeme().chars().last().is_some_and(char::is_alphanumeric);
+    if within_word || content.chars().next().is_none_or(char::is_whitespace) {
+        return None;
+    }
+
+    content.find(delimiter).and_then(|index| {
+        if content[..index].ends_with(char::is_whitespace) {
+            None
+        } else {
+            Some(2 * delimiter_length + content[..index].chars().count())
+        }
+    })
+}
+
+fn link_length(lexer: &Tokenizer) -> Option<usize> {
+    let line = rest_of_line(lexer);
+    let text_end = line.find(']')?;
+    if !line[text_end + 1..].starts_with('(') {
+        return None;
+    }
+    let url_end = line[text_end..].find(')')? + text_end;
+
+    Some(line[..url_end + 1].chars().count())
+}
+
+/// Lexes the given Markdown data, categorizing headings, emphasis,
+/// inline code, fenced code blocks, links and list markers.
+/// Everything else, including unmatched delimiters, is
+/// categorized as Category::Text.
+///
+/// # Examples
+///
+/// ```
+/// use luthor::lexers::markdown;
+/// use luthor::token::Category;
+///
+/// let tokens = markdown::lex("# luthor");
+/// assert_eq!(tokens[0].lexeme, "# luthor");
+/// assert_eq!(tokens[0].category, Category::Heading);
+/// ```
+pub fn lex(data: &str) -> Vec<Token> {
+    let mut lexer = new(data);
+    lexer.run(StateFunction::new(line_start));
+    lexer.into_tokens()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::lex;
+    use token::Category;
+
+    #[test]
+    fn it_can_handle_headings() {
+        let tokens = lex("## Title #1\n#hashtag");
+        let expected_tokens = [
+            ("## Title #1", Category::Heading),
+            ("\n", Category::Whitespace),
+            ("#hashtag", Category::Text),
+        ];
+
+        assert_eq!(tokens.len(), expected_tokens.len());
+        for (index, token) in tokens.iter().enumerate() {
+            assert_eq!(token.lexeme, expected_tokens[index].0);
+            assert_eq!(token.category, expected_tokens[index].1);
+        }
+    }
+
+    #[test]
+    fn it_can_handle_inline_code() {
+        let tokens = lex("run `cargo test` or ``a ` b``");
+        let expected_tokens = [
+            ("run ", Category::Text),
+            ("`cargo test`", Category::Code),
+            (" or ", Category::Text),
+            ("``a ` b``", Category::Code),
+        ];
+
+        assert_eq!(tokens.len(), expected_tokens.len());
+        for (index, token) in tokens.iter().enumerate() {
+            assert_eq!(token.lexeme, expected_tokens[index].0);
+            assert_eq!(token.category, expected_tokens[index].1);
+        }
+    }
+
+    #[test]
+    fn it_can_handle_links() {
+        let tokens = lex("see [luthor](https://example.com) [or not]");
+        let expected_tokens = [
+            ("see ", Category::Text),
+            ("[luthor](https://example.com)", Category::Link),
+            (" [or not]", Category::Text),
+        ];
+
+        assert_eq!(tokens.len(), expected_tokens.len());
+        for (index, token) in tokens.iter().enumerate() {
+            assert_eq!(token.lexeme, expected_tokens[index].0);
+            assert_eq!(token.category, expected_tokens[index].1);
+        }
+    }
+
+    #[test]
+    fn it_can_handle_bracketed_text_before_links() {
+        let tokens = lex("- [x] and [y](z)");
+        let expected_tokens = [
+            ("-", Category::ListMarker),
+            (" [x] and ", Category::Text),
+            ("[y](z)", Category::Link),
+        ];
+
+        assert_eq!(tokens.len(), expected_tokens.len());
+        for (index, token) in tokens.iter().enumerate() {
+            assert_eq!(token.lexeme, expected_tokens[index].0);
+            assert_eq!(token.category, expected_tokens[index].1);
+        }
+    }
+
+    #[test]
+    fn it_can_handle_emphasis_and_literal_delimiters() {
+        let tokens = lex("*a* **b** 2 * 3 snake_case_name *open");
+        let expected_tokens = [
+            ("*a*", Category::Emphasis),
+            (" ", Category::Text),
+            ("**b**", Category::Emphasis),
+            (" 2 * 3 snake_case_name *open", Category::Text),
+        ];
+
+        assert_eq!(tokens.len(), expected_tokens.len());
+        for (index, token) in tokens.iter().enumerate() {
+            assert_eq!(token.lexeme, expected_tokens[index].0);
+            assert_eq!(token.category, expected_tokens[index].1);
+        }
+    }
+
+    #[test]
+    fn it_can_handle_lists_and_fenced_code() {
+        let tokens = lex("- item\n  2. nested\n```rust\nlet a = *b*;\n```\nend");
+        let expected_tokens = [
+            ("-", Category::ListMarker),
+            (" item", Category::Text),
+            ("\n", Category::Whitespace),
+            ("  ", Category::Whitespace),
+            ("2.", Category::ListMarker),
+            (" nested", Category::Text),
+            ("\n", Category::Whitespace),
+            ("```rust\nlet a = *b*;\n```", Category::Code),
+            ("\n", Category::Whitespace),
+            ("end", Category::Text),
+        ];
+
+        assert_eq!(tokens.len(), expected_tokens.len());
+        for (index, token) in tokens.iter().enumerate() {
+            assert_eq!(token.lexeme, expected_tokens[index].0);
+            assert_eq!(token.category, expected_tokens[index].1);
+        }
+    }
+}
@@ -1 +1,256 @@
+use token::Token;
+use token::Category;
+use span::Span;
+
 pub mod json;
+pub mod xml;
+pub mod rust;
+pub mod css;
+pub mod markdown;
+pub mod shell;
+pub mod sql;
+pub mod toml;
+pub mod keyword;
+pub mod python;
+pub mod javascript;
+pub mod ini;
+pub mod csv;
+pub mod yaml;
+pub mod go;
+pub mod lua;
+pub mod regex;
+pub mod diff;
+pub mod simple;
+pub mod c;
+pub mod dockerfile;
+pub mod makefile;
+
+/// Lexes the given data one line at a time using the given line lexer,
+/// for line-oriented formats. The tokens of each line are adjusted to
+/// their position within the data, and are separated by newline tokens
+/// categorized as Category::Whitespace.
+///
+/// # Examples
+///
+/// ```
+/// use luthor::lexers::{lex_lines, keyword};
+/// use luthor::token::Category;
+///
+/// let lexer = keyword::KeywordLexer::new().keywords(&["end"]);
+/// let tokens = lex_lines("x\nend", |line| lexer.lex(line));
+/// assert_eq!(tokens[1].lexeme, "\n");
+/// assert_eq!(tokens[2].category, Category::Keyword);
+/// assert_eq!(tokens[2].span.start_line, 2);
+/// ```
+pub fn lex_lines<F>(data: &str, per_line: F) -> Vec<Token> where F: Fn(&str) -> Vec<Token> {
+    let mut tokens = Vec::new();
+    let mut offset = 0;
+    let mut previous_line_length = 0;
+
+    for (index, line) in data.split('\n').enumerate() {
+        if index > 0 {
+            // The newline ends the previous line, which is
+            // numbered by the index of the current line.
+            tokens.push(Token{
+                lexeme: "\n".to_string(),
+                category: Category::Whitespace,
+                start: offset,
+                end: offset + 1,
+                span: Span{
+                    start_line: index,
+                    start_col: previous_line_length + 1,
+                    end_line: index + 1,
+                    end_col: 1,
+                },
+            });
+            offset += 1;
+        }
+
+        for mut token in per_line(line) {
+            token.start += offset;
+            token.end += offset;
+            token.span.start_line += index;
+            token.span.end_line += index;
+            tokens.push(token);
+        }
+        previous_line_length = line.chars().count();
+        offset += previous_line_length;
+    }
+
+    tokens
+}
+
+/// Lexes the given data one segment at a time using the given segment
+/// lexer, splitting it at each occurrence of the delimiter. The tokens of
+/// each segment are adjusted to their position within the data, and are
+/// separated by delimiter tokens categorized as Category::Text.
+///
+/// # Examples
+///
+/// ```
+/// use luthor::lexers::{lex_split, keyword};
+/// use luthor::token::Category;
+///
+/// let lexer = keyword::KeywordLexer::new().keywords(&["usr"]);
+/// let tokens = lex_split("usr:bin", ':', |segment| lexer.lex(segment));
+/// assert_eq!(tokens[0].category, Category::Keyword);
+/// assert_eq!(tokens[1].lexeme, ":");
+/// assert_eq!(tokens[2].start, 4);
+/// ```
+pub fn lex_split<F>(data: &str, delimiter: char, per_segment: F) -> Vec<Token> where F: Fn(&str) -> Vec<Token> {
+    let mut tokens = Vec::new();
+    let mut offset = 0;
+    let mut line = 1;
+    let mut column = 1;
+
+    for (index, segment) in data.split(delimiter).enumerate() {
+        if index > 0 {
+            let (end_line, end_col) = position_after(&delimiter.to_string(), line, column);
+            tokens.push(Token{
+                lexeme: delimiter.to_string(),
+                category: Category::Text,
+                start: offset,
+                end: offset + 1,
+                span: Span{ start_line: line, start_col: column, end_line, end_col },
+            });
+            offset += 1;
+            line = end_line;
+            column = end_col;
+        }
+
+        for mut token in per_segment(segment) {
+            // Columns only need adjusting on the first line of the segment.
+            if token.span.start_line == 1 {
+                token.span.start_col += column - 1;
+            }
+            if token.span.end_line == 1 {
+                token.span.end_col += column - 1;
+            }
+            token.start += offset;
+            token.end += offset;
+            token.span.start_line += line - 1;
+            token.span.end_line += line - 1;
+            tokens.push(token);
+        }
+        let (end_line, end_col) = position_after(segment, line, column);
+        offset += segment.chars().count();
+        line = end_line;
+        column = end_col;
+    }
+
+    tokens
+}
+
+// Returns the line and column following the given
+// text, if it starts at the given line and column.
+fn position_after(text: &str, line: usize, column: usize) -> (usize, usize) {
+    text.chars().fold((line, column), |(line, column), c| {
+        if c == '\n' {
+            (line + 1, 1)
+        } else {
+            (line, column + 1)
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::lex_lines;
+    use super::lex_split;
+    use super::keyword::KeywordLexer;
+    use token::Token;
+    use token::Category;
+    use span::Span;
+
+    #[test]
+    fn lex_lines_separates_lines_with_newline_tokens() {
+        let lexer = KeywordLexer::new().keywords(&["fin"]);
+        let tokens = lex_lines("élé x\nfin", |line| lexer.lex(line));
+        let expected_tokens = vec![
+            Token{
+                lexeme: "élé".to_string(),
+                category: Category::Text,
+                start: 0,
+                end: 3,
+                span: Span{ start_line: 1, start_col: 1, end_line: 1, end_col: 4 },
+            },
+            Token{
+                lexeme: " ".to_string(),
+                category: Category::Whitespace,
+                start: 3,
+                end: 4,
+                span: Span{ start_line: 1, start_col: 4, end_line: 1, end_col: 5 },
+            },
+            Token{
+                lexeme: "x".to_string(),
+                category: Category::Text,
+                start: 4,
+                end: 5,
+                span: Span{ start_line: 1, start_col: 5, end_line: 1, end_col: 6 },
+            },
+            Token{
+                lexeme: "\n".to_string(),
+                category: Category::Whitespace,
+                start: 5,
+                end: 6,
+                span: Span{ start_line: 1, start_col: 6, end_line: 2, end_col: 1 },
+            },
+            Token{
+                lexeme: "fin".to_string(),
+                category: Category::Keyword,
+                start: 6,
+                end: 9,
+                span: Span{ start_line: 2, start_col: 1, end_line: 2, end_col: 4 },
+            },
+        ];
+
+        assert_eq!(tokens, expected_tokens);
+    }
+
+    #[test]
+    fn lex_lines_positions_tokens_as_if_lexing_the_data_as_a_whole() {
+        let lexer = KeywordLexer::new().keywords(&["fin"]).delimiters(";");
+        let data = "a;é\n\nfin\n";
+        let tokens = lex_lines(data, |line| lexer.lex(line));
+        let lexemes: Vec<&str> = tokens.iter().map(|token| &token.lexeme[..]).collect();
+
+        assert_eq!(lexemes, vec!["a", ";", "é", "\n", "\n", "fin", "\n"]);
+        assert_eq!(tokens[5], lexer.lex(data)[4]);
+    }
+
+    #[test]
+    fn lex_split_separates_segments_with_delimiter_tokens() {
+        let lexer = KeywordLexer::new().keywords(&["fin"]);
+        let tokens = lex_split("é x;fin;", ';', |segment| lexer.lex(segment));
+        let expected_tokens = [
+            ("é", Category::Text),
+            (" ", Category::Whitespace),
+            ("x", Category::Text),
+            (";", Category::Text),
+            ("fin", Category::Keyword),
+            (";", Category::Text),
+        ];
+
+        assert_eq!(tokens.len(), expected_tokens.len());
+        for (index, token) in tokens.iter().enumerate() {
+            assert_eq!(token.lexeme, expected_tokens[index].0);
+            assert_eq!(token.category, expected_tokens[index].1);
+        }
+    }
+
+    #[test]
+    fn lex_split_positions_tokens_as_if_lexing_the_data_as_a_whole() {
+        let lexer = KeywordLexer::new().keywords(&["fin"]);
+        let data = "a é;b\nfin;c";
+        let tokens = lex_split(data, ';', |segment| lexer.lex(segment));
+        let whole_tokens = lexer.lex(data);
+
+        assert_eq!(tokens[0], whole_tokens[0]);
+        assert_eq!(tokens[5], whole_tokens[3]);
+        assert_eq!(tokens[6].lexeme, "fin");
+        assert_eq!(tokens[6].span, Span{ start_line: 2, start_col: 1, end_line: 2, end_col: 4 });
+        assert_eq!(tokens[7].span, Span{ start_line: 2, start_col: 4, end_line: 2, end_col: 5 });
+        assert_eq!(tokens[8].start, 10);
+        assert_eq!(tokens[8].span, Span{ start_line: 2, start_col: 5, end_line: 2, end_col: 6 });
+    }
+}
@@ -0,0 +1,310 @@
+use tokenizer::new;
+use tokenizer::Tokenizer;
+use tokenizer::StateFunction;
+use token::Token;
+use token::Category;
+
+const KEYWORDS: [&str; 33] = [
+    "None", "and", "as", "assert", "async", "await", "break", "class",
+    "continue", "def", "del", "elif", "else", "except", "finally", "for",
+    "from", "global", "if", "import", "in", "is", "lambda", "nonlocal", "not",
+    "or", "pass", "raise", "return", "try", "while", "with", "yield",
+];
+
+const OPERATORS: [&str; 39] = [
+    "**=", "//=", ">>=", "<<=", "->", ":=", "**", "//", "<<", ">>", "<=", ">=",
+    "==", "!=", "+=", "-=", "*=", "/=", "%=", "&=", "|=", "^=", "@=", "+", "-",
+    "*", "/", "%", "@", "&", "|", "^", "~", "<", ">", "=", ".", ":", "!",
+];
+
+fn is_identifier_start(c: char) -> bool {
+    c.is_alphabetic() || c == '_'
+}
+
+fn is_identifier_char(c: char) -> bool {
+    c.is_alphanumeric() || c == '_'
+}
+
+fn is_blank(c: char) -> bool {
+    c == ' ' || c == '\t' || c == '\x0c'
+}
+
+fn line_start(lexer: &mut Tokenizer) -> Option<StateFunction> {
+    lexer.consume_while(is_blank);
+
+    // Blank lines don't affect the indentation level.
+    if lexer.current_char().is_none_or(|c| c == '\n' || c == '\r') {
+        lexer.tokenize(Category::Whitespace);
+    } else {
+        lexer.tokenize(Category::Indentation);
+    }
+
+    Some(code(0))
+}
+
+// Creates a state that lexes code. Lines can't end within brackets, so
+// the leading whitespace of the lines they span isn't indentation.
+fn code(depth: usize) -> StateFunction {
+    StateFunction::new(move |lexer: &mut Tokenizer| {
+        let c = lexer.current_char()?;
+        let mut depth = depth;
+
+        match c {
+            '\n' => {
+                lexer.tokenize_next(1, Category::Whitespace);
+                if depth == 0 {
+                    return Some(StateFunction::new(line_start));
+                }
+            },
+            '(' | '[' | '{' => {
+                depth += 1;
+                lexer.tokenize_next(1, bracket_category(c));
+            },
+            ')' | ']' | '}' => {
+                depth = depth.saturating_sub(1);
+                lexer.tokenize_next(1, bracket_category(c));
+            },
+            ',' | ';' => lexer.tokenize_next(1, Category::Punctuation),
+            '#' => {
+                lexer.consume_until(|c| c == '\n');
+                lexer.tokenize(Category::Comment);
+            },
+            '"' | '\'' => string(lexer),
+            _ => {
+                if c.is_whitespace() {
+                    lexer.consume_while(|c| c.is_whitespace() && c != '\n');
+                    lexer.tokenize(Category::Whitespace);
+                } else if c.is_ascii_digit() ||
+                    (c == '.' && lexer.peek_char().is_some_and(|c| c.is_ascii_digit())) {
+                    number(lexer);
+                } else if is_identifier_start(c) {
+                    identifier(lexer);
+                } else if !lexer.tokenize_match(&OPERATORS, Category::Operator) {
+                    lexer.tokenize_next(1, Category::Text);
+                }
+            }
+        }
+
+        Some(code(depth))
+    })
+}
+
+fn bracket_category(c: char) -> Category {
+    match c {
+        '(' | ')' => Category::Parenthesis,
+        '[' | ']' => Category::Bracket,
+        _ => Category::Brace,
+    }
+}
+
+fn identifier(lexer: &mut Tokenizer) {
+    lexer.consume_while(is_identifier_char);
+
+    // String literals can be prefixed with letters (e.g. rb"data").
+    let prefix = lexer.current_lexeme().to_lowercase();
+    let is_string_prefix = ["r", "u", "b", "f", "br", "rb", "fr", "rf"].contains(&prefix.as_str());
+    if is_string_prefix && lexer.current_char().is_some_and(|c| c == '"' || c == '\'') {
+        return string(lexer);
+    }
+
+    match lexer.current_lexeme() {
+        "True" | "False" => lexer.tokenize(Category::Boolean),
+        lexeme if KEYWORDS.contains(&lexeme) => lexer.tokenize(Category::Keyword),
+        _ => lexer.tokenize(Category::Identifier),
+    }
+}
+
+fn string(lexer: &mut Tokenizer) {
+    let quote = lexer.current_char().unwrap();
+    let triple_quote: String = [quote; 3].iter().collect();
+
+    if lexer.starts_with(&triple_quote) {
+        // Triple-quoted strings can span multiple lines.
+        lexer.advance_by(3);
+        while lexer.has_more_data() && !lexer.starts_with(&triple_quote) {
+            if lexer.current_char() == Some('\\') {
+                lexer.advance();
+            }
+            lexer.advance();
+        }
+        lexer.advance_by(3);
+    } else {
+        lexer.advance();
+        while let Some(c) = lexer.current_char() {
+            if c == '\n' {
+                break;
+            }
+            lexer.advance();
+            if c == quote {
+                break;
+            } else if c == '\\' {
+                lexer.advance();
+            }
+        }
+    }
+    lexer.tokenize(Category::String);
+}
+
+fn number(lexer: &mut Tokenizer) {
+    let mut category = Category::Integer;
+
+    if lexer.starts_with("0x") || lexer.starts_with("0X") || lexer.starts_with("0o") ||
+        lexer.starts_with("0O") || lexer.starts_with("0b") || lexer.starts_with("0B") {
+        lexer.advance_by(2);
+        lexer.consume_while(|c| c.is_ascii_hexdigit() || c == '_');
+    } else {
+        lexer.consume_while(|c| c.is_ascii_digit() || c == '_');
+        if lexer.current_char() == Some('.') {
+            lexer.advance();
+            lexer.consume_while(|c| c.is_ascii_digit() || c == '_');
+            category = Category::Float;
+        }
+        if lexer.current_char().is_some_and(|c| c == 'e' || c == 'E') {
+            let exponent_start = match lexer.peek_char() {
+                Some('+') | Some('-') => 2,
+                _ => 1,
+            };
+            if lexer.peek(exponent_start).is_some_and(|c| c.is_ascii_digit()) {
+                lexer.advance_by(exponent_start);
+                lexer.consume_while(|c| c.is_ascii_digit() || c == '_');
+                category = Category::Float;
+            }
+        }
+
+        // Imaginary numbers are suffixed with a j.
+        if lexer.accept("jJ") {
+            category = Category::Float;
+        }
+    }
+
+    lexer.tokenize(category);
+}
+
+/// Lexes the given Python source, categorizing keywords, identifiers,
+/// numbers, strings, comments and operators. The leading whitespace
+/// of each line is categorized as Category::Indentation, unless the
+/// line is blank or continues a bracketed expression.
+///
+/// # Examples
+///
+/// ```
+/// use luthor::lexers::python;
+/// use luthor::token::Category;
+///
+/// let tokens = python::lex("if x:\n    pass");
+/// assert_eq!(tokens[0].category, Category::Keyword);
+/// assert_eq!(tokens[5].lexeme, "    ");
+/// assert_eq!(tokens[5].category, Category::Indentation);
+/// ```
+pub fn lex(data: &str) -> Vec<Token> {
+    let mut lexer = new(data);
+    lexer.run(StateFunction::new(line_start));
+    lexer.into_tokens()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::lex;
+    use token::Category;
+
+    #[test]
+    fn it_can_handle_keywords() {
+        let tokens = lex("def is_none(x): return x is None or False");
+        let expected_tokens = [
+            ("def", Category::Keyword),
+            (" ", Category::Whitespace),
+            ("is_none", Category::Identifier),
+            ("(", Category::Parenthesis),
+            ("x", Category::Identifier),
+            (")", Category::Parenthesis),
+            (":", Category::Operator),
+            (" ", Category::Whitespace),
+            ("return", Category::Keyword),
+            (" ", Category::Whitespace),
+            ("x", Category::Identifier),
+            (" ", Category::Whitespace),
+            ("is", Category::Keyword),
+            (" ", Category::Whitespace),
+            ("None", Category::Keyword),
+            (" ", Category::Whitespace),
+            ("or", Category::Keyword),
+            (" ", Category::Whitespace),
+            ("False", Category::Boolean),
+        ];
+
+        assert_eq!(tokens.len(), expected_tokens.len());
+        for (index, token) in tokens.iter().enumerate() {
+            assert_eq!(token.lexeme, expected_tokens[index].0);
+            assert_eq!(token.category, expected_tokens[index].1);
+        }
+    }
+
+    #[test]
+    fn it_can_handle_triple_quoted_docstrings() {
+        let tokens = lex("def f():\n    \"\"\"Say \"hi\"\n    twice.\"\"\"\n    b'\\''");
+        let expected_tokens = [
+            ("def", Category::Keyword),
+            (" ", Category::Whitespace),
+            ("f", Category::Identifier),
+            ("(", Category::Parenthesis),
+            (")", Category::Parenthesis),
+            (":", Category::Operator),
+            ("\n", Category::Whitespace),
+            ("    ", Category::Indentation),
+            ("\"\"\"Say \"hi\"\n    twice.\"\"\"", Category::String),
+            ("\n", Category::Whitespace),
+            ("    ", Category::Indentation),
+            ("b'\\''", Category::String),
+        ];
+
+        assert_eq!(tokens.len(), expected_tokens.len());
+        for (index, token) in tokens.iter().enumerate() {
+            assert_eq!(token.lexeme, expected_tokens[index].0);
+            assert_eq!(token.category, expected_tokens[index].1);
+        }
+    }
+
+    #[test]
+    fn it_can_handle_indented_blocks() {
+        let tokens = lex("for i in (1,\n        2.5j):\n\tif i:\n  \n\t\tx **= 0x1F # ok");
+        let expected_tokens = [
+            ("for", Category::Keyword),
+            (" ", Category::Whitespace),
+            ("i", Category::Identifier),
+            (" ", Category::Whitespace),
+            ("in", Category::Keyword),
+            (" ", Category::Whitespace),
+            ("(", Category::Parenthesis),
+            ("1", Category::Integer),
+            (",", Category::Punctuation),
+            ("\n", Category::Whitespace),
+            ("        ", Category::Whitespace),
+            ("2.5j", Category::Float),
+            (")", Category::Parenthesis),
+            (":", Category::Operator),
+            ("\n", Category::Whitespace),
+            ("\t", Category::Indentation),
+            ("if", Category::Keyword),
+            (" ", Category::Whitespace),
+            ("i", Category::Identifier),
+            (":", Category::Operator),
+            ("\n", Category::Whitespace),
+            ("  ", Category::Whitespace),
+            ("\n", Category::Whitespace),
+            ("\t\t", Category::Indentation),
+            ("x", Category::Identifier),
+            (" ", Category::Whitespace),
+            ("**=", Category::Operator),
+            (" ", Category::Whitespace),
+            ("0x1F", Category::Integer),
+            (" ", Category::Whitespace),
+            ("# ok", Category::Comment),
+        ];
+
+        assert_eq!(tokens.len(), expected_tokens.len());
+        for (index, token) in tokens.iter().enumerate() {
+            assert_eq!(token.lexeme, expected_tokens[index].0);
+            assert_eq!(token.category, expected_tokens[index].1);
+        }
+    }
+}
@@ -0,0 +1,199 @@
+use tokenizer::new;
+use tokenizer::Tokenizer;
+use tokenizer::StateFunction;
+use token::Token;
+use token::Category;
+
+const ANCHOR_ESCAPES: &str = "bBAzZG";
+
+// Group prefixes that change the meaning of a group, which are
+// lexed as part of the opening parenthesis. Named groups are
+// handled separately, since their names vary.
+const GROUP_PREFIXES: [&str; 6] = ["(?<=", "(?<!", "(?:", "(?=", "(?!", "(?>"];
+
+fn is_literal_char(c: char) -> bool {
+    !"\\[](){*+?|^$.".contains(c)
+}
+
+fn initial_state(lexer: &mut Tokenizer) -> Option<StateFunction> {
+    let c = lexer.current_char()?;
+
+    match c {
+        '[' => class(lexer),
+        '\\' => {
+            let anchor = lexer.peek_char().is_some_and(|c| ANCHOR_ESCAPES.contains(c));
+            lexer.advance_by(2);
+            lexer.tokenize(if anchor { Category::Keyword } else { Category::Variable });
+        },
+        '(' => {
+            if !lexer.tokenize_match(&GROUP_PREFIXES, Category::Parenthesis) {
+                if lexer.starts_with("(?<") || lexer.starts_with("(?P<") {
+                    lexer.consume_until(|c| c == '>');
+                    lexer.advance();
+                    lexer.tokenize(Category::Parenthesis);
+                } else {
+                    lexer.tokenize_next(1, Category::Parenthesis);
+                }
+            }
+        },
+        ')' => lexer.tokenize_next(1, Category::Parenthesis),
+        '^' | '$' => lexer.tokenize_next(1, Category::Keyword),
+        '.' => lexer.tokenize_next(1, Category::Variable),
+        '|' => lexer.tokenize_next(1, Category::Operator),
+        '*' | '+' | '?' => {
+            lexer.advance();
+            quantifier_modifier(lexer);
+        },
+        '{' if bounded_quantifier_follows(lexer) => {
+            lexer.consume_until(|c| c == '}');
+            lexer.advance();
+            quantifier_modifier(lexer);
+        },
+        _ => {
+            // Braces that don't form a quantifier are literals.
+            lexer.advance();
+            lexer.consume_while(is_literal_char);
+            lexer.tokenize(Category::Text);
+        }
+    }
+
+    Some(StateFunction::new(initial_state))
+}
+
+// Lexes a character class, up to and including its closing bracket.
+// A closing bracket is literal if it's escaped, or if it's the first
+// character in the class (after an optional negating caret).
+fn class(lexer: &mut Tokenizer) {
+    lexer.advance();
+    lexer.accept("^");
+    lexer.accept("]");
+    while let Some(c) = lexer.current_char() {
+        lexer.advance();
+        if c == ']' {
+            break;
+        } else if c == '\\' {
+            lexer.advance();
+        }
+    }
+    lexer.tokenize(Category::String);
+}
+
+// Determines whether or not the brace at the current position starts
+// a bounded quantifier (e.g. {2}, {2,} or {2,5}), rather than a literal.
+fn bounded_quantifier_follows(lexer: &Tokenizer) -> bool {
+    let mut offset = 1;
+    let mut digits = 0;
+    let mut comma = false;
+
+    while let Some(c) = lexer.peek(offset) {
+        match c {
+            '}' => return digits > 0,
+            ',' if !comma && digits > 0 => comma = true,
+            c if c.is_ascii_digit() => digits += 1,
+            _ => return false,
+        }
+        offset += 1;
+    }
+
+    false
+}
+
+// Lexes a quantifier, including a trailing lazy (?) or possessive (+) modifier.
+fn quantifier_modifier(lexer: &mut Tokenizer) {
+    if !lexer.accept("?") {
+        lexer.accept("+");
+    }
+    lexer.tokenize(Category::Operator);
+}
+
+/// Lexes the given regular expression pattern. Character classes are
+/// categorized as strings, quantifiers and alternation as operators,
+/// groups as parentheses, anchors as keywords, escapes and the
+/// wildcard as variables, and everything else as literal text.
+///
+/// # Examples
+///
+/// ```
+/// use luthor::lexers::regex;
+/// use luthor::token::Category;
+///
+/// let tokens = regex::lex("^[a-z]+$");
+/// assert_eq!(tokens[1].lexeme, "[a-z]");
+/// assert_eq!(tokens[1].category, Category::String);
+/// assert_eq!(tokens[2].category, Category::Operator);
+/// ```
+pub fn lex(pattern: &str) -> Vec<Token> {
+    let mut lexer = new(pattern);
+    lexer.run(StateFunction::new(initial_state));
+    lexer.into_tokens()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::lex;
+    use token::Category;
+
+    #[test]
+    fn it_can_handle_escaped_brackets_in_classes() {
+        let tokens = lex("[^\\]a-]x[]b]\\d");
+        let expected_tokens = [
+            ("[^\\]a-]", Category::String),
+            ("x", Category::Text),
+            ("[]b]", Category::String),
+            ("\\d", Category::Variable),
+        ];
+
+        assert_eq!(tokens.len(), expected_tokens.len());
+        for (index, token) in tokens.iter().enumerate() {
+            assert_eq!(token.lexeme, expected_tokens[index].0);
+            assert_eq!(token.category, expected_tokens[index].1);
+        }
+    }
+
+    #[test]
+    fn it_can_handle_quantifiers() {
+        let tokens = lex("ab*?c{2,5}d{x}e+.?");
+        let expected_tokens = [
+            ("ab", Category::Text),
+            ("*?", Category::Operator),
+            ("c", Category::Text),
+            ("{2,5}", Category::Operator),
+            ("d", Category::Text),
+            ("{x}e", Category::Text),
+            ("+", Category::Operator),
+            (".", Category::Variable),
+            ("?", Category::Operator),
+        ];
+
+        assert_eq!(tokens.len(), expected_tokens.len());
+        for (index, token) in tokens.iter().enumerate() {
+            assert_eq!(token.lexeme, expected_tokens[index].0);
+            assert_eq!(token.category, expected_tokens[index].1);
+        }
+    }
+
+    #[test]
+    fn it_can_handle_groups_and_anchors() {
+        let tokens = lex("^(?<year>\\d{4})(?:-|/)\\b$");
+        let expected_tokens = [
+            ("^", Category::Keyword),
+            ("(?<year>", Category::Parenthesis),
+            ("\\d", Category::Variable),
+            ("{4}", Category::Operator),
+            (")", Category::Parenthesis),
+            ("(?:", Category::Parenthesis),
+            ("-", Category::Text),
+            ("|", Category::Operator),
+            ("/", Category::Text),
+            (")", Category::Parenthesis),
+            ("\\b", Category::Keyword),
+            ("$", Category::Keyword),
+        ];
+
+        assert_eq!(tokens.len(), expected_tokens.len());
+        for (index, token) in tokens.iter().enumerate() {
+            assert_eq!(token.lexeme, expected_tokens[index].0);
+            assert_eq!(token.category, expected_tokens[index].1);
+        }
+    }
+}
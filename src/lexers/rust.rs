@@ -0,0 +1,351 @@
+use tokenizer::new;
+use tokenizer::Tokenizer;
+use tokenizer::StateFunction;
+use token::Token;
+use token::Category;
+
+const KEYWORDS: [&str; 38] = [
+    "as", "async", "await", "break", "const", "continue", "crate", "dyn",
+    "else", "enum", "extern", "fn", "for", "if", "impl", "in", "let", "loop",
+    "match", "mod", "move", "mut", "pub", "ref", "return", "self", "Self",
+    "static", "struct", "super", "trait", "type", "union", "unsafe", "use",
+    "where", "while", "yield",
+];
+
+// Ordered so that longer operators are matched before their prefixes.
+const OPERATORS: [&str; 40] = [
+    "<<=", ">>=", "...", "..=", "::", "->", "=>", "==", "!=", "<=", ">=",
+    "&&", "||", "+=", "-=", "*=", "/=", "%=", "^=", "&=", "|=", "<<", ">>",
+    "..", "+", "-", "*", "/", "%", "^", "!", "&", "|", "=", "<", ">", "@",
+    ".", ":", "?",
+];
+
+fn is_identifier_start(c: char) -> bool {
+    c.is_alphabetic() || c == '_'
+}
+
+fn is_identifier_char(c: char) -> bool {
+    c.is_alphanumeric() || c == '_'
+}
+
+fn initial_state(lexer: &mut Tokenizer) -> Option<StateFunction> {
+    match lexer.current_char() {
+        Some(c) => {
+            match c {
+                '{' | '}' => lexer.tokenize_next(1, Category::Brace),
+                '[' | ']' => lexer.tokenize_next(1, Category::Bracket),
+                '(' | ')' => lexer.tokenize_next(1, Category::Parenthesis),
+                ';' | ',' | '#' => lexer.tokenize_next(1, Category::Punctuation),
+                '"' => return Some(StateFunction::new(string)),
+                '\'' => return Some(StateFunction::new(character_or_lifetime)),
+                _ => {
+                    if c.is_whitespace() {
+                        lexer.skip_whitespace();
+                    } else if lexer.starts_with("//") {
+                        return Some(StateFunction::new(line_comment));
+                    } else if lexer.starts_with("/*") {
+                        return Some(StateFunction::new(block_comment));
+                    } else if c.is_ascii_digit() {
+                        return Some(StateFunction::new(number));
+                    } else if is_identifier_start(c) {
+                        return Some(StateFunction::new(identifier));
+                    } else {
+                        match OPERATORS.iter().find(|operator| lexer.starts_with(operator)) {
+                            Some(operator) => lexer.tokenize_next(operator.len(), Category::Operator),
+                            None => lexer.tokenize_next(1, Category::Text),
+                        }
+                    }
+                }
+            }
+
+            Some(StateFunction::new(initial_state))
+        }
+
+        None => None
+    }
+}
+
+fn identifier(lexer: &mut Tokenizer) -> Option<StateFunction> {
+    // String and character literals can be prefixed with letters.
+    if lexer.starts_with("b'") {
+        lexer.advance();
+        return Some(StateFunction::new(character_or_lifetime));
+    } else if lexer.starts_with("b\"") {
+        lexer.advance();
+        return Some(StateFunction::new(string));
+    } else if lexer.starts_with("br\"") || lexer.starts_with("br#") {
+        lexer.advance();
+        return Some(StateFunction::new(raw_string));
+    } else if lexer.starts_with("r\"") || lexer.starts_with("r##") ||
+        (lexer.starts_with("r#") && lexer.peek(2) == Some('"')) {
+        return Some(StateFunction::new(raw_string));
+    } else if lexer.starts_with("r#") && lexer.peek(2).is_some_and(is_identifier_start) {
+        // Raw identifiers can use keywords as names.
+        lexer.advance_by(2);
+        lexer.consume_while(is_identifier_char);
+        lexer.tokenize(Category::Identifier);
+        return Some(StateFunction::new(initial_state));
+    }
+
+    lexer.consume_while(is_identifier_char);
+    if lexer.current_lexeme() == "true" || lexer.current_lexeme() == "false" {
+        lexer.tokenize(Category::Boolean);
+    } else if KEYWORDS.contains(&lexer.current_lexeme()) {
+        lexer.tokenize(Category::Keyword);
+    } else {
+        lexer.tokenize(Category::Identifier);
+    }
+
+    Some(StateFunction::new(initial_state))
+}
+
+fn number(lexer: &mut Tokenizer) -> Option<StateFunction> {
+    let mut category = Category::Integer;
+
+    if lexer.starts_with("0x") || lexer.starts_with("0o") || lexer.starts_with("0b") {
+        lexer.advance_by(2);
+        lexer.consume_while(|c| c.is_ascii_hexdigit() || c == '_');
+    } else {
+        lexer.consume_while(|c| c.is_ascii_digit() || c == '_');
+
+        // A dot followed by another dot or an identifier is
+        // a range or a method call, rather than a fraction.
+        if lexer.current_char() == Some('.') &&
+            !lexer.peek_char().is_some_and(|c| c == '.' || is_identifier_start(c)) {
+            lexer.advance();
+            lexer.consume_while(|c| c.is_ascii_digit() || c == '_');
+            category = Category::Float;
+        }
+        if lexer.current_char().is_some_and(|c| c == 'e' || c == 'E') {
+            let exponent_start = match lexer.peek_char() {
+                Some('+') | Some('-') => 2,
+                _ => 1,
+            };
+            if lexer.peek(exponent_start).is_some_and(|c| c.is_ascii_digit()) {
+                lexer.advance_by(exponent_start);
+                lexer.consume_while(|c| c.is_ascii_digit() || c == '_');
+                category = Category::Float;
+            }
+        }
+    }
+
+    // Type suffixes, such as u8 or f64, are part of the literal.
+    if lexer.current_char().is_some_and(is_identifier_start) {
+        if lexer.current_char() == Some('f') {
+            category = Category::Float;
+        }
+        lexer.consume_while(is_identifier_char);
+    }
+
+    lexer.tokenize(category);
+    Some(StateFunction::new(initial_state))
+}
+
+fn string(lexer: &mut Tokenizer) -> Option<StateFunction> {
+    lexer.advance();
+    while let Some(c) = lexer.current_char() {
+        lexer.advance();
+        match c {
+            '"' => break,
+            '\\' => lexer.advance(),
+            _ => (),
+        }
+    }
+    lexer.tokenize(Category::String);
+
+    Some(StateFunction::new(initial_state))
+}
+
+fn raw_string(lexer: &mut Tokenizer) -> Option<StateFunction> {
+    lexer.advance();
+    let mut terminator = String::from("\"");
+    while lexer.current_char() == Some('#') {
+        terminator.push('#');
+        lexer.advance();
+    }
+    lexer.advance();
+
+    while lexer.has_more_data() && !lexer.starts_with(&terminator) {
+        lexer.advance();
+    }
+    lexer.advance_by(terminator.len());
+    lexer.tokenize(Category::String);
+
+    Some(StateFunction::new(initial_state))
+}
+
+fn character_or_lifetime(lexer: &mut Tokenizer) -> Option<StateFunction> {
+    lexer.advance();
+    if lexer.current_char() == Some('\\') {
+        lexer.advance_by(2);
+        lexer.consume_until(|c| c == '\'' || c == '\n');
+        lexer.accept("'");
+        lexer.tokenize(Category::String);
+    } else if lexer.peek_char() == Some('\'') {
+        lexer.advance_by(2);
+        lexer.tokenize(Category::String);
+    } else {
+        lexer.consume_while(is_identifier_char);
+        lexer.tokenize(Category::Identifier);
+    }
+
+    Some(StateFunction::new(initial_state))
+}
+
+fn line_comment(lexer: &mut Tokenizer) -> Option<StateFunction> {
+    lexer.consume_until(|c| c == '\n');
+    lexer.tokenize(Category::Comment);
+    Some(StateFunction::new(initial_state))
+}
+
+fn block_comment(lexer: &mut Tokenizer) -> Option<StateFunction> {
+    let mut depth = 0;
+    while lexer.has_more_data() {
+        if lexer.starts_with("/*") {
+            depth += 1;
+            lexer.advance_by(2);
+        } else if lexer.starts_with("*/") {
+            depth -= 1;
+            lexer.advance_by(2);
+            if depth == 0 {
+                break;
+            }
+        } else {
+            lexer.advance();
+        }
+    }
+    lexer.tokenize(Category::Comment);
+
+    Some(StateFunction::new(initial_state))
+}
+
+/// Lexes the given Rust source, categorizing keywords, identifiers,
+/// numeric, string and character literals, comments and operators.
+///
+/// # Examples
+///
+/// ```
+/// use luthor::lexers::rust;
+/// use luthor::token::Category;
+///
+/// let tokens = rust::lex("fn main() {}");
+/// assert_eq!(tokens[0].lexeme, "fn");
+/// assert_eq!(tokens[0].category, Category::Keyword);
+/// ```
+pub fn lex(data: &str) -> Vec<Token> {
+    let mut lexer = new(data);
+    lexer.run(StateFunction::new(initial_state));
+    lexer.into_tokens()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::lex;
+    use token::Category;
+
+    #[test]
+    fn it_can_distinguish_keywords_from_identifiers() {
+        let tokens = lex("fn fnord(self) -> bool { true }");
+        let expected_tokens = [
+            ("fn", Category::Keyword),
+            (" ", Category::Whitespace),
+            ("fnord", Category::Identifier),
+            ("(", Category::Parenthesis),
+            ("self", Category::Keyword),
+            (")", Category::Parenthesis),
+            (" ", Category::Whitespace),
+            ("->", Category::Operator),
+            (" ", Category::Whitespace),
+            ("bool", Category::Identifier),
+            (" ", Category::Whitespace),
+            ("{", Category::Brace),
+            (" ", Category::Whitespace),
+            ("true", Category::Boolean),
+            (" ", Category::Whitespace),
+            ("}", Category::Brace),
+        ];
+
+        assert_eq!(tokens.len(), expected_tokens.len());
+        for (index, token) in tokens.iter().enumerate() {
+            assert_eq!(token.lexeme, expected_tokens[index].0);
+            assert_eq!(token.category, expected_tokens[index].1);
+        }
+    }
+
+    #[test]
+    fn it_can_handle_nested_block_comments() {
+        let tokens = lex("a /* outer /* inner */ still outer */ b // line\nc");
+        let expected_tokens = [
+            ("a", Category::Identifier),
+            (" ", Category::Whitespace),
+            ("/* outer /* inner */ still outer */", Category::Comment),
+            (" ", Category::Whitespace),
+            ("b", Category::Identifier),
+            (" ", Category::Whitespace),
+            ("// line", Category::Comment),
+            ("\n", Category::Whitespace),
+            ("c", Category::Identifier),
+        ];
+
+        assert_eq!(tokens.len(), expected_tokens.len());
+        for (index, token) in tokens.iter().enumerate() {
+            assert_eq!(token.lexeme, expected_tokens[index].0);
+            assert_eq!(token.category, expected_tokens[index].1);
+        }
+    }
+
+    #[test]
+    fn it_can_handle_strings_and_characters() {
+        let tokens = lex("(\"a \\\"b\\\"\", r#\"say \"hi\"\"#, '\\n', 'x', &'a str)");
+        let expected_tokens = [
+            ("(", Category::Parenthesis),
+            ("\"a \\\"b\\\"\"", Category::String),
+            (",", Category::Punctuation),
+            (" ", Category::Whitespace),
+            ("r#\"say \"hi\"\"#", Category::String),
+            (",", Category::Punctuation),
+            (" ", Category::Whitespace),
+            ("'\\n'", Category::String),
+            (",", Category::Punctuation),
+            (" ", Category::Whitespace),
+            ("'x'", Category::String),
+            (",", Category::Punctuation),
+            (" ", Category::Whitespace),
+            ("&", Category::Operator),
+            ("'a", Category::Identifier),
+            (" ", Category::Whitespace),
+            ("str", Category::Identifier),
+            (")", Category::Parenthesis),
+        ];
+
+        assert_eq!(tokens.len(), expected_tokens.len());
+        for (index, token) in tokens.iter().enumerate() {
+            assert_eq!(token.lexeme, expected_tokens[index].0);
+            assert_eq!(token.category, expected_tokens[index].1);
+        }
+    }
+
+    #[test]
+    fn it_can_handle_numbers() {
+        let tokens = lex("0xff_u8 1_000 2.5e-3 1f32 0..10");
+        let expected_tokens = [
+            ("0xff_u8", Category::Integer),
+            (" ", Category::Whitespace),
+            ("1_000", Category::Integer),
+            (" ", Category::Whitespace),
+            ("2.5e-3", Category::Float),
+            (" ", Category::Whitespace),
+            ("1f32", Category::Float),
+            (" ", Category::Whitespace),
+            ("0", Category::Integer),
+            ("..", Category::Operator),
+            ("10", Category::Integer),
+        ];
+
+        assert_eq!(tokens.len(), expected_tokens.len());
+        for (index, token) in tokens.iter().enumerate() {
+            assert_eq!(token.lexeme, expected_tokens[index].0);
+            assert_eq!(token.category, expected_tokens[index].1);
+        }
+    }
+}
@@ -0,0 +1,228 @@
+use tokenizer::new;
+use tokenizer::Tokenizer;
+use tokenizer::StateFunction;
+use token::Token;
+use token::Category;
+
+const KEYWORDS: [&str; 16] = [
+    "if", "then", "elif", "else", "fi", "for", "while", "until", "do", "done",
+    "case", "esac", "in", "function", "select", "time",
+];
+
+// Ordered so that longer operators are matched before their prefixes.
+const OPERATORS: [&str; 13] = [
+    "&&", "||", ";;", ">>", "<<", "&>", ">&", "|", "&", ";", ">", "<", "!",
+];
+
+fn is_word_char(c: char) -> bool {
+    !c.is_whitespace() && !"|&;<>()'\"$`".contains(c)
+}
+
+// Creates a state that lexes words and operators. Words in command
+// position (e.g. at the start of a line or after a pipe) are commands.
+fn words(command_position: bool) -> StateFunction {
+    StateFunction::new(move |lexer: &mut Tokenizer| {
+        let c = lexer.current_char()?;
+        let mut next_command_position = command_position;
+
+        if c.is_whitespace() {
+            lexer.skip_whitespace();
+            let newline = lexer.last_token().is_some_and(|token| token.lexeme.contains('\n'));
+            next_command_position = command_position || newline;
+        } else if c == '#' {
+            lexer.consume_until(|c| c == '\n');
+            lexer.tokenize(Category::Comment);
+        } else if c == '\'' {
+            lexer.advance();
+            lexer.consume_until(|c| c == '\'');
+            lexer.advance();
+            lexer.tokenize(Category::String);
+            next_command_position = false;
+        } else if c == '"' {
+            lexer.advance();
+            return Some(StateFunction::new(double_quoted));
+        } else if c == '$' && variable_follows(lexer) {
+            variable(lexer);
+            next_command_position = false;
+        } else if c == '(' || c == ')' {
+            lexer.tokenize_next(1, Category::Parenthesis);
+            next_command_position = c == '(';
+        } else if let Some(operator) = OPERATORS.iter().find(|operator| lexer.starts_with(operator)) {
+            lexer.tokenize_next(operator.len(), Category::Operator);
+
+            // Redirections are followed by a file name rather than a command.
+            next_command_position = !operator.contains(['<', '>']);
+        } else {
+            lexer.advance();
+            lexer.consume_while(is_word_char);
+
+            if command_position && KEYWORDS.contains(&lexer.current_lexeme()) {
+                lexer.tokenize(Category::Keyword);
+            } else if command_position && !lexer.current_lexeme().contains('=') {
+                lexer.tokenize(Category::Identifier);
+                next_command_position = false;
+            } else {
+                lexer.tokenize(Category::Text);
+            }
+        }
+
+        Some(words(next_command_position))
+    })
+}
+
+fn double_quoted(lexer: &mut Tokenizer) -> Option<StateFunction> {
+    loop {
+        match lexer.current_char() {
+            Some('"') => {
+                lexer.advance();
+                break;
+            },
+            Some('\\') => lexer.advance_by(2),
+            Some('$') if variable_follows(lexer) => {
+                // Variables are interpolated, so they're categorized separately.
+                lexer.tokenize(Category::String);
+                variable(lexer);
+            },
+            Some(_) => lexer.advance(),
+            None => break,
+        }
+    }
+    lexer.tokenize(Category::String);
+
+    Some(words(false))
+}
+
+// Determines whether or not the dollar sign at
+// the current position starts a variable reference.
+fn variable_follows(lexer: &Tokenizer) -> bool {
+    lexer.peek_char().is_some_and(|c| {
+        c == '{' || c == '_' || c.is_alphanumeric() || "@?$#!*-".contains(c)
+    })
+}
+
+fn variable(lexer: &mut Tokenizer) {
+    match lexer.peek_char() {
+        Some('{') => {
+            lexer.consume_until(|c| c == '}');
+            lexer.advance();
+        },
+        Some(c) if c.is_alphabetic() || c == '_' => {
+            lexer.advance();
+            lexer.consume_while(|c| c.is_alphanumeric() || c == '_');
+        },
+        _ => lexer.advance_by(2),
+    }
+    lexer.tokenize(Category::Variable);
+}
+
+/// Lexes the given shell script, categorizing keywords, commands,
+/// variable references, quoted strings, comments and operators.
+/// Variables within double-quoted strings are categorized separately.
+///
+/// # Examples
+///
+/// ```
+/// use luthor::lexers::shell;
+/// use luthor::token::Category;
+///
+/// let tokens = shell::lex("echo $HOME");
+/// assert_eq!(tokens[0].category, Category::Identifier);
+/// assert_eq!(tokens[2].lexeme, "$HOME");
+/// assert_eq!(tokens[2].category, Category::Variable);
+/// ```
+pub fn lex(data: &str) -> Vec<Token> {
+    let mut lexer = new(data);
+    lexer.run(words(true));
+    lexer.into_tokens()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::lex;
+    use token::Category;
+
+    #[test]
+    fn it_can_handle_piped_commands() {
+        let tokens = lex("ls -la | grep foo >> out.txt && echo done # ok");
+        let expected_tokens = [
+            ("ls", Category::Identifier),
+            (" ", Category::Whitespace),
+            ("-la", Category::Text),
+            (" ", Category::Whitespace),
+            ("|", Category::Operator),
+            (" ", Category::Whitespace),
+            ("grep", Category::Identifier),
+            (" ", Category::Whitespace),
+            ("foo", Category::Text),
+            (" ", Category::Whitespace),
+            (">>", Category::Operator),
+            (" ", Category::Whitespace),
+            ("out.txt", Category::Text),
+            (" ", Category::Whitespace),
+            ("&&", Category::Operator),
+            (" ", Category::Whitespace),
+            ("echo", Category::Identifier),
+            (" ", Category::Whitespace),
+            ("done", Category::Text),
+            (" ", Category::Whitespace),
+            ("# ok", Category::Comment),
+        ];
+
+        assert_eq!(tokens.len(), expected_tokens.len());
+        for (index, token) in tokens.iter().enumerate() {
+            assert_eq!(token.lexeme, expected_tokens[index].0);
+            assert_eq!(token.category, expected_tokens[index].1);
+        }
+    }
+
+    #[test]
+    fn it_can_handle_variable_references() {
+        let tokens = lex("cd ${HOME}/bin$1\nif true; then PATH=$x; fi");
+        let expected_tokens = [
+            ("cd", Category::Identifier),
+            (" ", Category::Whitespace),
+            ("${HOME}", Category::Variable),
+            ("/bin", Category::Text),
+            ("$1", Category::Variable),
+            ("\n", Category::Whitespace),
+            ("if", Category::Keyword),
+            (" ", Category::Whitespace),
+            ("true", Category::Identifier),
+            (";", Category::Operator),
+            (" ", Category::Whitespace),
+            ("then", Category::Keyword),
+            (" ", Category::Whitespace),
+            ("PATH=", Category::Text),
+            ("$x", Category::Variable),
+            (";", Category::Operator),
+            (" ", Category::Whitespace),
+            ("fi", Category::Keyword),
+        ];
+
+        assert_eq!(tokens.len(), expected_tokens.len());
+        for (index, token) in tokens.iter().enumerate() {
+            assert_eq!(token.lexeme, expected_tokens[index].0);
+            assert_eq!(token.category, expected_tokens[index].1);
+        }
+    }
+
+    #[test]
+    fn it_only_interpolates_variables_in_double_quotes() {
+        let tokens = lex("echo '$HOME' \"at $HOME \\$5 $\"");
+        let expected_tokens = [
+            ("echo", Category::Identifier),
+            (" ", Category::Whitespace),
+            ("'$HOME'", Category::String),
+            (" ", Category::Whitespace),
+            ("\"at ", Category::String),
+            ("$HOME", Category::Variable),
+            (" \\$5 $\"", Category::String),
+        ];
+
+        assert_eq!(tokens.len(), expected_tokens.len());
+        for (index, token) in tokens.iter().enumerate() {
+            assert_eq!(token.lexeme, expected_tokens[index].0);
+            assert_eq!(token.category, expected_tokens[index].1);
+        }
+    }
+}
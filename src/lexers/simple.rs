@@ -0,0 +1,164 @@
+use lexers::keyword::KeywordLexer;
+use token::Token;
+
+/// The SimpleLexer type is a configurable lexer that splits data into
+/// words, delimiters and whitespace. Unlike the KeywordLexer, it also
+/// allows the characters that are treated as whitespace to be chosen.
+#[derive(Debug, Clone, Default)]
+pub struct SimpleLexer {
+    lexer: KeywordLexer,
+    whitespace: Option<String>,
+}
+
+impl SimpleLexer {
+    /// Initializes a new lexer without any keywords or delimiters,
+    /// which treats all Unicode whitespace characters as whitespace.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use luthor::lexers::simple::SimpleLexer;
+    ///
+    /// let lexer = SimpleLexer::new();
+    /// ```
+    pub fn new() -> SimpleLexer {
+        SimpleLexer::default()
+    }
+
+    /// Adds the given words to the lexer's keyword set.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use luthor::lexers::simple::SimpleLexer;
+    /// use luthor::token::Category;
+    ///
+    /// let lexer = SimpleLexer::new().keywords(&["let", "in"]);
+    /// assert_eq!(lexer.lex("let")[0].category, Category::Keyword);
+    /// ```
+    pub fn keywords(mut self, keywords: &[&str]) -> SimpleLexer {
+        self.lexer = self.lexer.keywords(keywords);
+        self
+    }
+
+    /// Adds the given characters to the lexer's delimiters, which end
+    /// words and are categorized as punctuation, one token apiece.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use luthor::lexers::simple::SimpleLexer;
+    /// use luthor::token::Category;
+    ///
+    /// let lexer = SimpleLexer::new().delimiters("+-");
+    /// let tokens = lexer.lex("a+b");
+    /// assert_eq!(tokens[1].lexeme, "+");
+    /// assert_eq!(tokens[1].category, Category::Punctuation);
+    /// ```
+    pub fn delimiters(mut self, delimiters: &str) -> SimpleLexer {
+        self.lexer = self.lexer.delimiters(delimiters);
+        self
+    }
+
+    /// Adds the given characters to the lexer's whitespace, which end words
+    /// and are categorized as whitespace, with consecutive characters sharing
+    /// a token. Once called, only the characters given are whitespace.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use luthor::lexers::simple::SimpleLexer;
+    /// use luthor::token::Category;
+    ///
+    /// let lexer = SimpleLexer::new().whitespace("_");
+    /// let tokens = lexer.lex("a__b c");
+    /// assert_eq!(tokens[1].lexeme, "__");
+    /// assert_eq!(tokens[1].category, Category::Whitespace);
+    /// assert_eq!(tokens[2].lexeme, "b c");
+    /// ```
+    pub fn whitespace(mut self, whitespace: &str) -> SimpleLexer {
+        self.whitespace.get_or_insert_with(String::new).push_str(whitespace);
+        self
+    }
+
+    fn is_whitespace(&self, c: char) -> bool {
+        match self.whitespace {
+            Some(ref whitespace) => whitespace.contains(c),
+            None => c.is_whitespace(),
+        }
+    }
+
+    /// Lexes the given data, splitting it into words at whitespace
+    /// and delimiters. Words in the keyword set are categorized
+    /// as keywords, and all other words as text.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use luthor::lexers::simple::SimpleLexer;
+    /// use luthor::token::Category;
+    ///
+    /// let lexer = SimpleLexer::new().keywords(&["if"]);
+    /// let tokens = lexer.lex("if luthor");
+    /// assert_eq!(tokens[2].lexeme, "luthor");
+    /// assert_eq!(tokens[2].category, Category::Text);
+    /// ```
+    pub fn lex(&self, data: &str) -> Vec<Token> {
+        self.lexer.lex_with_whitespace(data, |c| self.is_whitespace(c))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::SimpleLexer;
+    use token::Category;
+
+    #[test]
+    fn it_splits_words_at_delimiters() {
+        let lexer = SimpleLexer::new()
+            .delimiters("+-")
+            .keywords(&["mod"]);
+        let tokens = lexer.lex("a+-b - c mod\td");
+        let expected_tokens = [
+            ("a", Category::Text),
+            ("+", Category::Punctuation),
+            ("-", Category::Punctuation),
+            ("b", Category::Text),
+            (" ", Category::Whitespace),
+            ("-", Category::Punctuation),
+            (" ", Category::Whitespace),
+            ("c", Category::Text),
+            (" ", Category::Whitespace),
+            ("mod", Category::Keyword),
+            ("\t", Category::Whitespace),
+            ("d", Category::Text),
+        ];
+
+        assert_eq!(tokens.len(), expected_tokens.len());
+        for (index, token) in tokens.iter().enumerate() {
+            assert_eq!(token.lexeme, expected_tokens[index].0);
+            assert_eq!(token.category, expected_tokens[index].1);
+        }
+    }
+
+    #[test]
+    fn it_only_treats_the_given_whitespace_as_whitespace() {
+        let lexer = SimpleLexer::new()
+            .delimiters("+-")
+            .whitespace(",")
+            .whitespace(";");
+        let tokens = lexer.lex("a b,;+c");
+        let expected_tokens = [
+            ("a b", Category::Text),
+            (",;", Category::Whitespace),
+            ("+", Category::Punctuation),
+            ("c", Category::Text),
+        ];
+
+        assert_eq!(tokens.len(), expected_tokens.len());
+        for (index, token) in tokens.iter().enumerate() {
+            assert_eq!(token.lexeme, expected_tokens[index].0);
+            assert_eq!(token.category, expected_tokens[index].1);
+        }
+    }
+}
@@ -0,0 +1,197 @@
+use tokenizer::new;
+use tokenizer::Tokenizer;
+use tokenizer::StateFunction;
+use token::Token;
+use token::Category;
+
+const KEYWORDS: [&str; 60] = [
+    "add", "all", "alter", "and", "as", "asc", "between", "by", "case",
+    "check", "column", "constraint", "create", "cross", "default", "delete",
+    "desc", "distinct", "drop", "else", "end", "exists", "foreign", "from",
+    "full", "group", "having", "in", "index", "inner", "insert", "into", "is",
+    "join", "key", "left", "like", "limit", "not", "null", "offset", "on",
+    "or", "order", "outer", "primary", "references", "right", "select",
+    "set", "table", "then", "union", "unique", "update", "values", "view",
+    "when", "where", "with",
+];
+
+// Ordered so that longer operators are matched before their prefixes.
+const OPERATORS: [&str; 15] = [
+    "<>", "!=", "<=", ">=", "||", "::", "=", "<", ">", "+", "-", "*", "/", "%", "|",
+];
+
+fn is_identifier_char(c: char) -> bool {
+    c.is_alphanumeric() || c == '_' || c == '$'
+}
+
+fn initial_state(lexer: &mut Tokenizer) -> Option<StateFunction> {
+    let c = lexer.current_char()?;
+
+    if c.is_whitespace() {
+        lexer.skip_whitespace();
+    } else if lexer.starts_with("--") {
+        lexer.consume_until(|c| c == '\n');
+        lexer.tokenize(Category::Comment);
+    } else if lexer.starts_with("/*") {
+        lexer.advance_by(2);
+        while lexer.has_more_data() && !lexer.starts_with("*/") {
+            lexer.advance();
+        }
+        lexer.advance_by(2);
+        lexer.tokenize(Category::Comment);
+    } else if c == '\'' {
+        quoted(lexer, '\'', Category::String);
+    } else if c == '"' || c == '`' {
+        quoted(lexer, c, Category::Identifier);
+    } else if c == '(' || c == ')' {
+        lexer.tokenize_next(1, Category::Parenthesis);
+    } else if c == ',' || c == ';' || c == '.' {
+        lexer.tokenize_next(1, Category::Punctuation);
+    } else if c.is_ascii_digit() {
+        lexer.consume_while(|c| c.is_ascii_digit());
+        if lexer.current_char() == Some('.') && lexer.peek_char().is_some_and(|c| c.is_ascii_digit()) {
+            lexer.advance();
+            lexer.consume_while(|c| c.is_ascii_digit());
+            lexer.tokenize(Category::Float);
+        } else {
+            lexer.tokenize(Category::Integer);
+        }
+    } else if c.is_alphabetic() || c == '_' {
+        lexer.consume_while(is_identifier_char);
+        let word = lexer.current_lexeme();
+
+        if word.eq_ignore_ascii_case("true") || word.eq_ignore_ascii_case("false") {
+            lexer.tokenize(Category::Boolean);
+        } else if KEYWORDS.iter().any(|keyword| keyword.eq_ignore_ascii_case(word)) {
+            lexer.tokenize(Category::Keyword);
+        } else {
+            lexer.tokenize(Category::Identifier);
+        }
+    } else {
+        match OPERATORS.iter().find(|operator| lexer.starts_with(operator)) {
+            Some(operator) => lexer.tokenize_next(operator.len(), Category::Operator),
+            None => lexer.tokenize_next(1, Category::Text),
+        }
+    }
+
+    Some(StateFunction::new(initial_state))
+}
+
+// Tokenizes a quoted value, in which the quote
+// is escaped by doubling it (e.g. 'it''s').
+fn quoted(lexer: &mut Tokenizer, quote: char, category: Category) {
+    lexer.advance();
+    while let Some(c) = lexer.current_char() {
+        lexer.advance();
+        if c == quote {
+            if lexer.current_char() == Some(quote) {
+                lexer.advance();
+            } else {
+                break;
+            }
+        }
+    }
+    lexer.tokenize(category);
+}
+
+/// Lexes the given SQL, categorizing keywords (regardless of their case),
+/// identifiers, string and numeric literals, comments and operators.
+///
+/// # Examples
+///
+/// ```
+/// use luthor::lexers::sql;
+/// use luthor::token::Category;
+///
+/// let tokens = sql::lex("Select 1");
+/// assert_eq!(tokens[0].category, Category::Keyword);
+/// assert_eq!(tokens[2].category, Category::Integer);
+/// ```
+pub fn lex(data: &str) -> Vec<Token> {
+    let mut lexer = new(data);
+    lexer.run(StateFunction::new(initial_state));
+    lexer.into_tokens()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::lex;
+    use token::Category;
+
+    #[test]
+    fn it_can_handle_mixed_case_keywords() {
+        let tokens = lex("SELECT name FroM users where id>=2.5");
+        let expected_tokens = [
+            ("SELECT", Category::Keyword),
+            (" ", Category::Whitespace),
+            ("name", Category::Identifier),
+            (" ", Category::Whitespace),
+            ("FroM", Category::Keyword),
+            (" ", Category::Whitespace),
+            ("users", Category::Identifier),
+            (" ", Category::Whitespace),
+            ("where", Category::Keyword),
+            (" ", Category::Whitespace),
+            ("id", Category::Identifier),
+            (">=", Category::Operator),
+            ("2.5", Category::Float),
+        ];
+
+        assert_eq!(tokens.len(), expected_tokens.len());
+        for (index, token) in tokens.iter().enumerate() {
+            assert_eq!(token.lexeme, expected_tokens[index].0);
+            assert_eq!(token.category, expected_tokens[index].1);
+        }
+    }
+
+    #[test]
+    fn it_can_handle_strings_with_escaped_quotes() {
+        let tokens = lex("INSERT INTO t VALUES('it''s', \"a\"\"b\");");
+        let expected_tokens = [
+            ("INSERT", Category::Keyword),
+            (" ", Category::Whitespace),
+            ("INTO", Category::Keyword),
+            (" ", Category::Whitespace),
+            ("t", Category::Identifier),
+            (" ", Category::Whitespace),
+            ("VALUES", Category::Keyword),
+            ("(", Category::Parenthesis),
+            ("'it''s'", Category::String),
+            (",", Category::Punctuation),
+            (" ", Category::Whitespace),
+            ("\"a\"\"b\"", Category::Identifier),
+            (")", Category::Parenthesis),
+            (";", Category::Punctuation),
+        ];
+
+        assert_eq!(tokens.len(), expected_tokens.len());
+        for (index, token) in tokens.iter().enumerate() {
+            assert_eq!(token.lexeme, expected_tokens[index].0);
+            assert_eq!(token.category, expected_tokens[index].1);
+        }
+    }
+
+    #[test]
+    fn it_can_handle_comments() {
+        let tokens = lex("-- all rows\nselect * /* everything */ from t");
+        let expected_tokens = [
+            ("-- all rows", Category::Comment),
+            ("\n", Category::Whitespace),
+            ("select", Category::Keyword),
+            (" ", Category::Whitespace),
+            ("*", Category::Operator),
+            (" ", Category::Whitespace),
+            ("/* everything */", Category::Comment),
+            (" ", Category::Whitespace),
+            ("from", Category::Keyword),
+            (" ", Category::Whitespace),
+            ("t", Category::Identifier),
+        ];
+
+        assert_eq!(tokens.len(), expected_tokens.len());
+        for (index, token) in tokens.iter().enumerate() {
+            assert_eq!(token.lexeme, expected_tokens[index].0);
+            assert_eq!(token.category, expected_tokens[index].1);
+        }
+    }
+}
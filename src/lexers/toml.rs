@@ -0,0 +1,342 @@
+use tokenizer::new;
+use tokenizer::Tokenizer;
+use tokenizer::StateFunction;
+use token::Token;
+use token::Category;
+
+fn is_bare_key_char(c: char) -> bool {
+    c.is_alphanumeric() || c == '_' || c == '-'
+}
+
+fn is_digit(c: char) -> bool {
+    c.is_ascii_digit()
+}
+
+// Tokenizes spaces and tabs, leaving newlines, which end keys and values.
+fn skip_blanks(lexer: &mut Tokenizer) {
+    lexer.tokenize(Category::Text);
+    lexer.consume_while(|c| c == ' ' || c == '\t');
+    lexer.tokenize(Category::Whitespace);
+}
+
+fn comment(lexer: &mut Tokenizer) {
+    lexer.consume_until(|c| c == '\n');
+    lexer.tokenize(Category::Comment);
+}
+
+fn line_start(lexer: &mut Tokenizer) -> Option<StateFunction> {
+    let c = lexer.current_char()?;
+
+    if c.is_whitespace() {
+        lexer.skip_whitespace();
+    } else if c == '#' {
+        comment(lexer);
+    } else if c == '[' {
+        // Table headers, including those of arrays of tables (e.g. [[a]]).
+        lexer.consume_until(|c| c == ']' || c == '\n');
+        lexer.accept("]");
+        lexer.accept("]");
+        lexer.tokenize(Category::Heading);
+    } else {
+        return Some(StateFunction::new(key));
+    }
+
+    Some(StateFunction::new(line_start))
+}
+
+fn key(lexer: &mut Tokenizer) -> Option<StateFunction> {
+    match lexer.current_char() {
+        Some(c) => {
+            if c == ' ' || c == '\t' {
+                skip_blanks(lexer);
+            } else if c == '=' {
+                lexer.tokenize_next(1, Category::AssignmentOperator);
+                return Some(value(0));
+            } else if c == '.' {
+                lexer.tokenize_next(1, Category::Punctuation);
+            } else if c == '"' || c == '\'' {
+                string(lexer);
+            } else if is_bare_key_char(c) {
+                lexer.consume_while(is_bare_key_char);
+                lexer.tokenize(Category::Identifier);
+            } else if c == '\n' {
+                return Some(StateFunction::new(line_start));
+            } else {
+                lexer.tokenize_next(1, Category::Text);
+            }
+
+            Some(StateFunction::new(key))
+        }
+
+        None => None
+    }
+}
+
+// Creates a state that lexes a value, which can span multiple
+// lines when it's nested within arrays or inline tables.
+fn value(depth: usize) -> StateFunction {
+    StateFunction::new(move |lexer: &mut Tokenizer| {
+        let c = lexer.current_char()?;
+        let mut depth = depth;
+
+        match c {
+            '\n' if depth == 0 => return Some(StateFunction::new(line_start)),
+            '[' | ']' | '{' | '}' => {
+                if c == '[' || c == '{' {
+                    depth += 1;
+                } else {
+                    depth = depth.saturating_sub(1);
+                }
+                let category = if c == '[' || c == ']' { Category::Bracket } else { Category::Brace };
+                lexer.tokenize_next(1, category);
+            },
+            ',' => lexer.tokenize_next(1, Category::Punctuation),
+            '=' => lexer.tokenize_next(1, Category::AssignmentOperator),
+            '#' => comment(lexer),
+            '"' | '\'' => string(lexer),
+            _ => {
+                if c.is_whitespace() {
+                    lexer.consume_while(|c| c.is_whitespace() && (depth > 0 || c != '\n'));
+                    lexer.tokenize(Category::Whitespace);
+                } else if datetime_follows(lexer) {
+                    datetime(lexer);
+                } else if is_digit(c) || ((c == '+' || c == '-') && lexer.peek_char().is_some_and(|c| is_digit(c) || c == 'i' || c == 'n')) {
+                    number(lexer);
+                } else if is_bare_key_char(c) {
+                    lexer.consume_while(is_bare_key_char);
+                    match lexer.current_lexeme() {
+                        "true" | "false" => lexer.tokenize(Category::Boolean),
+                        "inf" | "nan" => lexer.tokenize(Category::Float),
+                        // Bare words within inline tables are keys.
+                        _ => lexer.tokenize(Category::Identifier),
+                    }
+                } else {
+                    lexer.tokenize_next(1, Category::Text);
+                }
+            }
+        }
+
+        Some(value(depth))
+    })
+}
+
+fn string(lexer: &mut Tokenizer) {
+    let quote = lexer.current_char().unwrap();
+    let escapes = quote == '"';
+
+    if (escapes && lexer.starts_with("\"\"\"")) || lexer.starts_with("'''") {
+        // Multi-line strings end with the first unescaped triple quote.
+        let delimiter = if escapes { "\"\"\"" } else { "'''" };
+        lexer.advance_by(3);
+        while lexer.has_more_data() && !lexer.starts_with(delimiter) {
+            if escapes && lexer.current_char() == Some('\\') {
+                lexer.advance();
+            }
+            lexer.advance();
+        }
+        lexer.advance_by(3);
+    } else {
+        lexer.advance();
+        while let Some(c) = lexer.current_char() {
+            if c == '\n' {
+                break;
+            }
+            lexer.advance();
+            if c == quote {
+                break;
+            } else if escapes && c == '\\' {
+                lexer.advance();
+            }
+        }
+    }
+    lexer.tokenize(Category::String);
+}
+
+// Determines whether the upcoming data is a date (e.g. 1979-05-27)
+// or a time (e.g. 07:32:00), both of which start like numbers.
+fn datetime_follows(lexer: &Tokenizer) -> bool {
+    let date: [fn(char) -> bool; 5] = [is_digit, is_digit, is_digit, is_digit, |c| c == '-'];
+    let time: [fn(char) -> bool; 3] = [is_digit, is_digit, |c| c == ':'];
+
+    lexer.peek_matches(&date) || lexer.peek_matches(&time)
+}
+
+fn datetime(lexer: &mut Tokenizer) {
+    let is_datetime_char = |c: char| is_digit(c) || "-:.TZtz+".contains(c);
+    lexer.consume_while(is_datetime_char);
+
+    // Dates and times can also be separated by a space.
+    if lexer.current_char() == Some(' ') && lexer.peek(1).is_some_and(is_digit) &&
+        lexer.peek(2).is_some_and(is_digit) && lexer.peek(3) == Some(':') {
+        lexer.advance();
+        lexer.consume_while(is_datetime_char);
+    }
+    lexer.tokenize(Category::DateTime);
+}
+
+fn number(lexer: &mut Tokenizer) {
+    let mut category = Category::Integer;
+    lexer.accept("+-");
+
+    if lexer.starts_with("inf") || lexer.starts_with("nan") {
+        lexer.advance_by(3);
+        category = Category::Float;
+    } else if lexer.starts_with("0x") || lexer.starts_with("0o") || lexer.starts_with("0b") {
+        lexer.advance_by(2);
+        lexer.consume_while(|c| c.is_ascii_hexdigit() || c == '_');
+    } else {
+        lexer.consume_while(|c| is_digit(c) || c == '_');
+        if lexer.current_char() == Some('.') && lexer.peek_char().is_some_and(is_digit) {
+            lexer.advance();
+            lexer.consume_while(|c| is_digit(c) || c == '_');
+            category = Category::Float;
+        }
+        if lexer.accept("eE") {
+            lexer.accept("+-");
+            lexer.consume_while(|c| is_digit(c) || c == '_');
+            category = Category::Float;
+        }
+    }
+    lexer.tokenize(category);
+}
+
+/// Lexes the given TOML data, categorizing table headers, keys, values
+/// (including multi-line strings and dates and times), and comments.
+///
+/// # Examples
+///
+/// ```
+/// use luthor::lexers::toml;
+/// use luthor::token::Category;
+///
+/// let tokens = toml::lex("version = 1");
+/// assert_eq!(tokens[0].category, Category::Identifier);
+/// assert_eq!(tokens[4].category, Category::Integer);
+/// ```
+pub fn lex(data: &str) -> Vec<Token> {
+    let mut lexer = new(data);
+    lexer.run(StateFunction::new(line_start));
+    lexer.into_tokens()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::lex;
+    use token::Category;
+
+    #[test]
+    fn it_can_handle_table_headers() {
+        let tokens = lex("[package]\nname = \"luthor\"\n[[bin]]\nsite.\"url\" = 'a'");
+        let expected_tokens = [
+            ("[package]", Category::Heading),
+            ("\n", Category::Whitespace),
+            ("name", Category::Identifier),
+            (" ", Category::Whitespace),
+            ("=", Category::AssignmentOperator),
+            (" ", Category::Whitespace),
+            ("\"luthor\"", Category::String),
+            ("\n", Category::Whitespace),
+            ("[[bin]]", Category::Heading),
+            ("\n", Category::Whitespace),
+            ("site", Category::Identifier),
+            (".", Category::Punctuation),
+            ("\"url\"", Category::String),
+            (" ", Category::Whitespace),
+            ("=", Category::AssignmentOperator),
+            (" ", Category::Whitespace),
+            ("'a'", Category::String),
+        ];
+
+        assert_eq!(tokens.len(), expected_tokens.len());
+        for (index, token) in tokens.iter().enumerate() {
+            assert_eq!(token.lexeme, expected_tokens[index].0);
+            assert_eq!(token.category, expected_tokens[index].1);
+        }
+    }
+
+    #[test]
+    fn it_can_handle_multi_line_strings() {
+        let tokens = lex("a = \"\"\"\none \\\"\"\" two\n\"\"\"\nb = 1");
+        let expected_tokens = [
+            ("a", Category::Identifier),
+            (" ", Category::Whitespace),
+            ("=", Category::AssignmentOperator),
+            (" ", Category::Whitespace),
+            ("\"\"\"\none \\\"\"\" two\n\"\"\"", Category::String),
+            ("\n", Category::Whitespace),
+            ("b", Category::Identifier),
+            (" ", Category::Whitespace),
+            ("=", Category::AssignmentOperator),
+            (" ", Category::Whitespace),
+            ("1", Category::Integer),
+        ];
+
+        assert_eq!(tokens.len(), expected_tokens.len());
+        for (index, token) in tokens.iter().enumerate() {
+            assert_eq!(token.lexeme, expected_tokens[index].0);
+            assert_eq!(token.category, expected_tokens[index].1);
+        }
+    }
+
+    #[test]
+    fn it_can_handle_comments() {
+        let tokens = lex("# config\nok = true # inline");
+        let expected_tokens = [
+            ("# config", Category::Comment),
+            ("\n", Category::Whitespace),
+            ("ok", Category::Identifier),
+            (" ", Category::Whitespace),
+            ("=", Category::AssignmentOperator),
+            (" ", Category::Whitespace),
+            ("true", Category::Boolean),
+            (" ", Category::Whitespace),
+            ("# inline", Category::Comment),
+        ];
+
+        assert_eq!(tokens.len(), expected_tokens.len());
+        for (index, token) in tokens.iter().enumerate() {
+            assert_eq!(token.lexeme, expected_tokens[index].0);
+            assert_eq!(token.category, expected_tokens[index].1);
+        }
+    }
+
+    #[test]
+    fn it_can_handle_arrays_numbers_and_datetimes() {
+        let tokens = lex("v = [\n  1_000, -2.5e3,\n  1979-05-27 07:32:00Z, { x = 0x1f },\n]");
+        let expected_tokens = [
+            ("v", Category::Identifier),
+            (" ", Category::Whitespace),
+            ("=", Category::AssignmentOperator),
+            (" ", Category::Whitespace),
+            ("[", Category::Bracket),
+            ("\n  ", Category::Whitespace),
+            ("1_000", Category::Integer),
+            (",", Category::Punctuation),
+            (" ", Category::Whitespace),
+            ("-2.5e3", Category::Float),
+            (",", Category::Punctuation),
+            ("\n  ", Category::Whitespace),
+            ("1979-05-27 07:32:00Z", Category::DateTime),
+            (",", Category::Punctuation),
+            (" ", Category::Whitespace),
+            ("{", Category::Brace),
+            (" ", Category::Whitespace),
+            ("x", Category::Identifier),
+            (" ", Category::Whitespace),
+            ("=", Category::AssignmentOperator),
+            (" ", Category::Whitespace),
+            ("0x1f", Category::Integer),
+            (" ", Category::Whitespace),
+            ("}", Category::Brace),
+            (",", Category::Punctuation),
+            ("\n", Category::Whitespace),
+            ("]", Category::Bracket),
+        ];
+
+        assert_eq!(tokens.len(), expected_tokens.len());
+        for (index, token) in tokens.iter().enumerate() {
+            assert_eq!(token.lexeme, expected_tokens[index].0);
+            assert_eq!(token.category, expected_tokens[index].1);
+        }
+    }
+}
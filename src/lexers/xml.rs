@@ -0,0 +1,235 @@
+use tokenizer::new;
+use tokenizer::Tokenizer;
+use tokenizer::StateFunction;
+use token::Token;
+use token::Category;
+
+fn is_name_start(c: char) -> bool {
+    c.is_alphabetic() || c == '_' || c == ':'
+}
+
+fn is_name_char(c: char) -> bool {
+    c.is_alphanumeric() || c == '_' || c == ':' || c == '-' || c == '.'
+}
+
+fn initial_state(lexer: &mut Tokenizer) -> Option<StateFunction> {
+    match lexer.current_char() {
+        Some('<') => {
+            if lexer.starts_with("<!--") {
+                lexer.tokenize(Category::Text);
+                return Some(StateFunction::new(comment));
+            } else if lexer.starts_with("</") || lexer.starts_with("<?") ||
+                lexer.starts_with("<!") {
+                lexer.tokenize_next(2, Category::Tag);
+                return Some(StateFunction::new(element_name));
+            } else if lexer.peek_char().is_some_and(is_name_start) {
+                lexer.tokenize_next(1, Category::Tag);
+                return Some(StateFunction::new(element_name));
+            }
+
+            // A stray angle bracket is treated as text content.
+            lexer.advance();
+            Some(StateFunction::new(initial_state))
+        }
+
+        Some(_) => {
+            lexer.advance();
+            Some(StateFunction::new(initial_state))
+        }
+
+        None => {
+            lexer.tokenize(Category::Text);
+            None
+        }
+    }
+}
+
+fn element_name(lexer: &mut Tokenizer) -> Option<StateFunction> {
+    lexer.consume_while(is_name_char);
+    lexer.tokenize(Category::Identifier);
+    Some(StateFunction::new(inside_tag))
+}
+
+fn inside_tag(lexer: &mut Tokenizer) -> Option<StateFunction> {
+    match lexer.current_char() {
+        Some(c) => {
+            match c {
+                '>' => {
+                    lexer.tokenize_next(1, Category::Tag);
+                    return Some(StateFunction::new(initial_state));
+                },
+                '/' | '?' if lexer.peek_char() == Some('>') => {
+                    lexer.tokenize_next(2, Category::Tag);
+                    return Some(StateFunction::new(initial_state));
+                },
+                '=' => {
+                    lexer.tokenize_next(1, Category::AssignmentOperator);
+                },
+                '"' | '\'' => {
+                    return Some(StateFunction::new(attribute_value));
+                },
+                _ => {
+                    if c.is_whitespace() {
+                        lexer.skip_whitespace();
+                    } else if is_name_start(c) {
+                        lexer.consume_while(is_name_char);
+                        lexer.tokenize(Category::Attribute);
+                    } else {
+                        lexer.tokenize_next(1, Category::Text);
+                    }
+                }
+            }
+
+            Some(StateFunction::new(inside_tag))
+        }
+
+        None => None
+    }
+}
+
+fn attribute_value(lexer: &mut Tokenizer) -> Option<StateFunction> {
+    let quote = lexer.current_char().unwrap();
+    lexer.advance();
+    lexer.consume_until(|c| c == quote);
+    lexer.advance();
+    lexer.tokenize(Category::String);
+    Some(StateFunction::new(inside_tag))
+}
+
+fn comment(lexer: &mut Tokenizer) -> Option<StateFunction> {
+    lexer.advance_by(4);
+    while lexer.has_more_data() && !lexer.starts_with("-->") {
+        lexer.advance();
+    }
+    lexer.advance_by(3);
+    lexer.tokenize(Category::Comment);
+    Some(StateFunction::new(initial_state))
+}
+
+/// Lexes the given XML or HTML data, categorizing tag delimiters,
+/// element names, attributes and their values, and comments.
+/// Text content and malformed markup are categorized as Category::Text.
+///
+/// # Examples
+///
+/// ```
+/// use luthor::lexers::xml;
+/// use luthor::token::Category;
+///
+/// let tokens = xml::lex("<p>luthor</p>");
+/// assert_eq!(tokens[1].lexeme, "p");
+/// assert_eq!(tokens[1].category, Category::Identifier);
+/// ```
+pub fn lex(data: &str) -> Vec<Token> {
+    let mut lexer = new(data);
+    lexer.run(StateFunction::new(initial_state));
+    lexer.into_tokens()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::lex;
+    use token::Category;
+
+    #[test]
+    fn it_can_handle_self_closing_tags() {
+        let tokens = lex("<a><br/></a>");
+        let expected_tokens = [
+            ("<", Category::Tag),
+            ("a", Category::Identifier),
+            (">", Category::Tag),
+            ("<", Category::Tag),
+            ("br", Category::Identifier),
+            ("/>", Category::Tag),
+            ("</", Category::Tag),
+            ("a", Category::Identifier),
+            (">", Category::Tag),
+        ];
+
+        assert_eq!(tokens.len(), expected_tokens.len());
+        for (index, token) in tokens.iter().enumerate() {
+            assert_eq!(token.lexeme, expected_tokens[index].0);
+            assert_eq!(token.category, expected_tokens[index].1);
+        }
+    }
+
+    #[test]
+    fn it_can_handle_attributes_with_quoted_values() {
+        let tokens = lex("<a href=\"/lu thor\" data-x='1'>link</a>");
+        let expected_tokens = [
+            ("<", Category::Tag),
+            ("a", Category::Identifier),
+            (" ", Category::Whitespace),
+            ("href", Category::Attribute),
+            ("=", Category::AssignmentOperator),
+            ("\"/lu thor\"", Category::String),
+            (" ", Category::Whitespace),
+            ("data-x", Category::Attribute),
+            ("=", Category::AssignmentOperator),
+            ("'1'", Category::String),
+            (">", Category::Tag),
+            ("link", Category::Text),
+            ("</", Category::Tag),
+            ("a", Category::Identifier),
+            (">", Category::Tag),
+        ];
+
+        assert_eq!(tokens.len(), expected_tokens.len());
+        for (index, token) in tokens.iter().enumerate() {
+            assert_eq!(token.lexeme, expected_tokens[index].0);
+            assert_eq!(token.category, expected_tokens[index].1);
+        }
+    }
+
+    #[test]
+    fn it_can_handle_comments() {
+        let tokens = lex("a<!-- <b> -->c");
+        let expected_tokens = [
+            ("a", Category::Text),
+            ("<!-- <b> -->", Category::Comment),
+            ("c", Category::Text),
+        ];
+
+        assert_eq!(tokens.len(), expected_tokens.len());
+        for (index, token) in tokens.iter().enumerate() {
+            assert_eq!(token.lexeme, expected_tokens[index].0);
+            assert_eq!(token.category, expected_tokens[index].1);
+        }
+    }
+
+    #[test]
+    fn it_can_handle_unterminated_comments() {
+        let tokens = lex("<p><!-- open -");
+        let expected_tokens = [
+            ("<", Category::Tag),
+            ("p", Category::Identifier),
+            (">", Category::Tag),
+            ("<!-- open -", Category::Comment),
+        ];
+
+        assert_eq!(tokens.len(), expected_tokens.len());
+        for (index, token) in tokens.iter().enumerate() {
+            assert_eq!(token.lexeme, expected_tokens[index].0);
+            assert_eq!(token.category, expected_tokens[index].1);
+        }
+    }
+
+    #[test]
+    fn it_can_handle_malformed_markup() {
+        let tokens = lex("1 < 2 <a %>");
+        let expected_tokens = [
+            ("1 < 2 ", Category::Text),
+            ("<", Category::Tag),
+            ("a", Category::Identifier),
+            (" ", Category::Whitespace),
+            ("%", Category::Text),
+            (">", Category::Tag),
+        ];
+
+        assert_eq!(tokens.len(), expected_tokens.len());
+        for (index, token) in tokens.iter().enumerate() {
+            assert_eq!(token.lexeme, expected_tokens[index].0);
+            assert_eq!(token.category, expected_tokens[index].1);
+        }
+    }
+}
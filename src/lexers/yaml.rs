@@ -0,0 +1,354 @@
+use tokenizer::new;
+use tokenizer::Tokenizer;
+use tokenizer::StateFunction;
+use token::Token;
+use token::Category;
+
+fn line_start(lexer: &mut Tokenizer) -> Option<StateFunction> {
+    let indentation = lexer.advance_while_char(' ');
+
+    // Blank and comment-only lines don't affect the indentation level.
+    if lexer.current_char().is_none_or(|c| c == '\n' || c == '\r' || c == '#') {
+        lexer.tokenize(Category::Whitespace);
+    } else {
+        lexer.tokenize(Category::Indentation);
+    }
+
+    if indentation == 0 && (lexer.starts_with("---") || lexer.starts_with("...")) &&
+        lexer.peek(3).is_none_or(char::is_whitespace) {
+        lexer.tokenize_next(3, Category::Punctuation);
+    }
+
+    Some(node(indentation, 0))
+}
+
+// Creates a state that lexes the nodes on a line with the given
+// indentation. Flow collections can span lines, so newlines within
+// them don't start new lines, in terms of indentation.
+fn node(indentation: usize, depth: usize) -> StateFunction {
+    StateFunction::new(move |lexer: &mut Tokenizer| {
+        let c = lexer.current_char()?;
+        let mut depth = depth;
+
+        match c {
+            '\n' if depth == 0 => {
+                lexer.tokenize_next(1, Category::Whitespace);
+                return Some(StateFunction::new(line_start));
+            },
+            '{' | '[' => {
+                depth += 1;
+                let category = if c == '{' { Category::Brace } else { Category::Bracket };
+                lexer.tokenize_next(1, category);
+            },
+            '}' | ']' => {
+                depth = depth.saturating_sub(1);
+                let category = if c == '}' { Category::Brace } else { Category::Bracket };
+                lexer.tokenize_next(1, category);
+            },
+            ',' if depth > 0 => lexer.tokenize_next(1, Category::Punctuation),
+            '#' => {
+                lexer.consume_until(|c| c == '\n');
+                lexer.tokenize(Category::Comment);
+            },
+            '"' => double_quoted(lexer),
+            '\'' => single_quoted(lexer),
+            '&' | '*' | '!' => {
+                // Anchors, aliases and tags run up to the next whitespace.
+                lexer.consume_until(|c| c.is_whitespace() || (depth > 0 && ",[]{}".contains(c)));
+                let category = if c == '!' { Category::Tag } else { Category::Variable };
+                lexer.tokenize(category);
+            },
+            '|' | '>' if depth == 0 => block_scalar(lexer, indentation),
+            _ => {
+                if c.is_whitespace() {
+                    lexer.consume_while(|c| c.is_whitespace() && (depth > 0 || c != '\n'));
+                    lexer.tokenize(Category::Whitespace);
+                } else if c == '-' && depth == 0 && lexer.peek_char().is_none_or(char::is_whitespace) {
+                    lexer.tokenize_next(1, Category::ListMarker);
+                } else if c == ':' && indicator_follows(lexer, depth) {
+                    lexer.tokenize_next(1, Category::AssignmentOperator);
+                } else {
+                    plain_scalar(lexer, depth);
+                }
+            }
+        }
+
+        Some(node(indentation, depth))
+    })
+}
+
+// Determines whether or not the character after the current one
+// ends a plain scalar, making the current one an indicator.
+fn indicator_follows(lexer: &Tokenizer, depth: usize) -> bool {
+    lexer.peek_char().is_none_or(|c| c.is_whitespace() || (depth > 0 && ",[]{}".contains(c)))
+}
+
+fn plain_scalar(lexer: &mut Tokenizer, depth: usize) {
+    while let Some(c) = lexer.current_char() {
+        let ends_scalar = c == '\n' ||
+            (c == ':' && indicator_follows(lexer, depth)) ||
+            (c.is_whitespace() && lexer.peek_char() == Some('#')) ||
+            (depth > 0 && ",[]{}".contains(c));
+        if ends_scalar {
+            break;
+        }
+        lexer.advance();
+    }
+
+    // Trailing whitespace isn't part of the scalar.
+    while lexer.current_lexeme().ends_with(char::is_whitespace) {
+        lexer.backup();
+    }
+
+    // Scalars followed by a colon are mapping keys.
+    if lexer.current_char() == Some(':') && indicator_follows(lexer, depth) {
+        lexer.tokenize(Category::Identifier);
+    } else {
+        let category = scalar_category(lexer.current_lexeme());
+        lexer.tokenize(category);
+    }
+}
+
+fn scalar_category(scalar: &str) -> Category {
+    match scalar {
+        "true" | "True" | "TRUE" | "false" | "False" | "FALSE" => Category::Boolean,
+        "null" | "Null" | "NULL" | "~" => Category::Keyword,
+        ".inf" | "-.inf" | "+.inf" | ".nan" => Category::Float,
+        _ => {
+            let digits = scalar.trim_start_matches(['-', '+']);
+            if digits.is_empty() || !digits.starts_with(|c: char| c.is_ascii_digit() || c == '.') {
+                Category::String
+            } else if digits.starts_with("0x") || digits.starts_with("0o") {
+                if digits[2..].chars().all(|c| c.is_ascii_hexdigit()) { Category::Integer } else { Category::String }
+            } else if digits.chars().all(|c| c.is_ascii_digit()) {
+                Category::Integer
+            } else if digits.parse::<f64>().is_ok() {
+                Category::Float
+            } else {
+                Category::String
+            }
+        }
+    }
+}
+
+fn double_quoted(lexer: &mut Tokenizer) {
+    lexer.advance();
+    while let Some(c) = lexer.current_char() {
+        lexer.advance();
+        match c {
+            '"' => break,
+            '\\' => lexer.advance(),
+            _ => (),
+        }
+    }
+    lexer.tokenize(Category::String);
+}
+
+fn single_quoted(lexer: &mut Tokenizer) {
+    lexer.advance();
+
+    // Single quotes are escaped by doubling them (e.g. 'it''s').
+    while lexer.advance_to('\'') {
+        lexer.advance();
+        if !lexer.accept("'") {
+            break;
+        }
+    }
+    lexer.tokenize(Category::String);
+}
+
+// Lexes a literal (|) or folded (>) block scalar, whose content is
+// made up of the following lines indented beyond the current one.
+fn block_scalar(lexer: &mut Tokenizer, indentation: usize) {
+    lexer.advance();
+    lexer.accept("+-");
+    lexer.accept("123456789");
+    lexer.accept("+-");
+    lexer.tokenize(Category::Operator);
+
+    lexer.consume_while(|c| c == ' ' || c == '\t');
+    lexer.tokenize(Category::Whitespace);
+    if lexer.current_char() == Some('#') {
+        lexer.consume_until(|c| c == '\n');
+        lexer.tokenize(Category::Comment);
+    }
+    if lexer.current_char() != Some('\n') {
+        return;
+    }
+    lexer.tokenize_next(1, Category::Whitespace);
+
+    // The content ends with its last non-blank line,
+    // excluding that line's trailing line break.
+    let mut length = 0;
+    let mut content_length = 0;
+    for line in lexer.remaining().split_inclusive('\n') {
+        let content = line.trim_end();
+        if !content.is_empty() {
+            if content.chars().take_while(|&c| c == ' ').count() <= indentation {
+                break;
+            }
+            content_length = length + content.chars().count();
+        }
+        length += line.chars().count();
+    }
+    lexer.tokenize_next(content_length, Category::String);
+}
+
+/// Lexes the given YAML data, categorizing mapping keys, list markers,
+/// scalars (including quoted strings and block scalars), flow collections,
+/// comments, anchors, aliases and tags. The leading whitespace of each
+/// line is categorized as Category::Indentation, unless the line is blank.
+///
+/// # Examples
+///
+/// ```
+/// use luthor::lexers::yaml;
+/// use luthor::token::Category;
+///
+/// let tokens = yaml::lex("name: luthor");
+/// assert_eq!(tokens[0].category, Category::Identifier);
+/// assert_eq!(tokens[3].lexeme, "luthor");
+/// assert_eq!(tokens[3].category, Category::String);
+/// ```
+pub fn lex(data: &str) -> Vec<Token> {
+    let mut lexer = new(data);
+    lexer.run(StateFunction::new(line_start));
+    lexer.into_tokens()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::lex;
+    use token::Category;
+
+    #[test]
+    fn it_can_handle_block_mappings() {
+        let tokens = lex("---\nserver:\n  port: 8080\n  hosts:\n    - a b\n    - 'it''s'\n  debug: ~");
+        let expected_tokens = [
+            ("---", Category::Punctuation),
+            ("\n", Category::Whitespace),
+            ("server", Category::Identifier),
+            (":", Category::AssignmentOperator),
+            ("\n", Category::Whitespace),
+            ("  ", Category::Indentation),
+            ("port", Category::Identifier),
+            (":", Category::AssignmentOperator),
+            (" ", Category::Whitespace),
+            ("8080", Category::Integer),
+            ("\n", Category::Whitespace),
+            ("  ", Category::Indentation),
+            ("hosts", Category::Identifier),
+            (":", Category::AssignmentOperator),
+            ("\n", Category::Whitespace),
+            ("    ", Category::Indentation),
+            ("-", Category::ListMarker),
+            (" ", Category::Whitespace),
+            ("a b", Category::String),
+            ("\n", Category::Whitespace),
+            ("    ", Category::Indentation),
+            ("-", Category::ListMarker),
+            (" ", Category::Whitespace),
+            ("'it''s'", Category::String),
+            ("\n", Category::Whitespace),
+            ("  ", Category::Indentation),
+            ("debug", Category::Identifier),
+            (":", Category::AssignmentOperator),
+            (" ", Category::Whitespace),
+            ("~", Category::Keyword),
+        ];
+
+        assert_eq!(tokens.len(), expected_tokens.len());
+        for (index, token) in tokens.iter().enumerate() {
+            assert_eq!(token.lexeme, expected_tokens[index].0);
+            assert_eq!(token.category, expected_tokens[index].1);
+        }
+    }
+
+    #[test]
+    fn it_can_handle_flow_collections() {
+        let tokens = lex("a: [1, 2.5,\n  {b: true, c: \"d\"}]");
+        let expected_tokens = [
+            ("a", Category::Identifier),
+            (":", Category::AssignmentOperator),
+            (" ", Category::Whitespace),
+            ("[", Category::Bracket),
+            ("1", Category::Integer),
+            (",", Category::Punctuation),
+            (" ", Category::Whitespace),
+            ("2.5", Category::Float),
+            (",", Category::Punctuation),
+            ("\n  ", Category::Whitespace),
+            ("{", Category::Brace),
+            ("b", Category::Identifier),
+            (":", Category::AssignmentOperator),
+            (" ", Category::Whitespace),
+            ("true", Category::Boolean),
+            (",", Category::Punctuation),
+            (" ", Category::Whitespace),
+            ("c", Category::Identifier),
+            (":", Category::AssignmentOperator),
+            (" ", Category::Whitespace),
+            ("\"d\"", Category::String),
+            ("}", Category::Brace),
+            ("]", Category::Bracket),
+        ];
+
+        assert_eq!(tokens.len(), expected_tokens.len());
+        for (index, token) in tokens.iter().enumerate() {
+            assert_eq!(token.lexeme, expected_tokens[index].0);
+            assert_eq!(token.category, expected_tokens[index].1);
+        }
+    }
+
+    #[test]
+    fn it_can_handle_comments() {
+        let tokens = lex("# top\nurl: http://a#b # note");
+        let expected_tokens = [
+            ("# top", Category::Comment),
+            ("\n", Category::Whitespace),
+            ("url", Category::Identifier),
+            (":", Category::AssignmentOperator),
+            (" ", Category::Whitespace),
+            ("http://a#b", Category::String),
+            (" ", Category::Whitespace),
+            ("# note", Category::Comment),
+        ];
+
+        assert_eq!(tokens.len(), expected_tokens.len());
+        for (index, token) in tokens.iter().enumerate() {
+            assert_eq!(token.lexeme, expected_tokens[index].0);
+            assert_eq!(token.category, expected_tokens[index].1);
+        }
+    }
+
+    #[test]
+    fn it_can_handle_block_scalars() {
+        let tokens = lex("a:\n  script: |-\n    echo: 1\n\n    done\n  b: >\n    c");
+        let expected_tokens = [
+            ("a", Category::Identifier),
+            (":", Category::AssignmentOperator),
+            ("\n", Category::Whitespace),
+            ("  ", Category::Indentation),
+            ("script", Category::Identifier),
+            (":", Category::AssignmentOperator),
+            (" ", Category::Whitespace),
+            ("|-", Category::Operator),
+            ("\n", Category::Whitespace),
+            ("    echo: 1\n\n    done", Category::String),
+            ("\n", Category::Whitespace),
+            ("  ", Category::Indentation),
+            ("b", Category::Identifier),
+            (":", Category::AssignmentOperator),
+            (" ", Category::Whitespace),
+            (">", Category::Operator),
+            ("\n", Category::Whitespace),
+            ("    c", Category::String),
+        ];
+
+        assert_eq!(tokens.len(), expected_tokens.len());
+        for (index, token) in tokens.iter().enumerate() {
+            assert_eq!(token.lexeme, expected_tokens[index].0);
+            assert_eq!(token.category, expected_tokens[index].1);
+        }
+    }
+}
@@ -1,3 +1,16 @@
+#[cfg(feature = "serde")]
+#[macro_use]
+extern crate serde;
+#[cfg(test)]
+#[cfg(feature = "serde")]
+extern crate serde_json;
+#[cfg(feature = "unicode-segmentation")]
+extern crate unicode_segmentation;
+
+pub mod error;
+pub mod highlight;
 pub mod lexers;
+pub mod registry;
+pub mod span;
 pub mod token;
 pub mod tokenizer;
@@ -0,0 +1,181 @@
+use std::collections::HashMap;
+use tokenizer::Tokenizer;
+use tokenizer::StateFunction;
+use token::Token;
+use token::Category;
+
+// A registered lexer, either the initial state of a lexer to be run
+// by the registry, or a lex function such as those of the built-in lexers.
+enum Lexer<C> {
+    State(StateFunction<C>),
+    Function(fn(&str) -> Vec<Token<C>>),
+}
+
+/// The Registry type maps names (e.g. languages or file extensions) to
+/// lexers, so that data can be lexed by name, without the caller
+/// needing to know which lexer to use.
+pub struct Registry<C = Category> {
+    lexers: HashMap<String, Lexer<C>>,
+}
+
+impl<C> Default for Registry<C> {
+    fn default() -> Registry<C> {
+        Registry{ lexers: HashMap::new() }
+    }
+}
+
+impl<C> Registry<C> {
+    /// Initializes a new registry without any lexers.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use luthor::registry::Registry;
+    ///
+    /// let mut registry: Registry = Registry::new();
+    /// assert!(registry.lex_named("json", "{}").is_none());
+    /// ```
+    pub fn new() -> Registry<C> {
+        Registry::default()
+    }
+
+    /// Registers the given function or closure as the initial state of the
+    /// lexer with the given name, replacing any lexer previously registered
+    /// with it. As a state function, it's invoked each time data is lexed
+    /// using the name, so it shouldn't depend on having been invoked before.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use luthor::registry::Registry;
+    /// use luthor::token::Category;
+    /// use luthor::tokenizer::{StateFunction, Tokenizer};
+    ///
+    /// fn initial_state(lexer: &mut Tokenizer) -> Option<StateFunction> {
+    ///     lexer.tokenize_remaining(Category::Text);
+    ///     None
+    /// }
+    ///
+    /// let mut registry = Registry::new();
+    /// registry.register("text", initial_state);
+    /// ```
+    pub fn register<F>(&mut self, name: &str, state: F)
+        where F: FnMut(&mut Tokenizer<C>) -> Option<StateFunction<C>> + 'static {
+        self.lexers.insert(name.to_string(), Lexer::State(StateFunction::new(state)));
+    }
+
+    /// Registers the given lex function (e.g. one of the built-in lexers)
+    /// as the lexer with the given name, replacing any lexer previously
+    /// registered with it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use luthor::lexers::json;
+    /// use luthor::registry::Registry;
+    /// use luthor::token::Category;
+    ///
+    /// let mut registry = Registry::new();
+    /// registry.register_fn("json", json::lex);
+    /// assert_eq!(registry.lex_named("json", "{}").unwrap()[0].category, Category::Brace);
+    /// ```
+    pub fn register_fn(&mut self, name: &str, lex: fn(&str) -> Vec<Token<C>>) {
+        self.lexers.insert(name.to_string(), Lexer::Function(lex));
+    }
+
+    /// Lexes the given data using the lexer registered with the given
+    /// name, returning its tokens, or None if no such lexer is registered.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use luthor::registry::Registry;
+    /// use luthor::token::Category;
+    /// use luthor::tokenizer::{StateFunction, Tokenizer};
+    ///
+    /// fn initial_state(lexer: &mut Tokenizer) -> Option<StateFunction> {
+    ///     lexer.tokenize_remaining(Category::Text);
+    ///     None
+    /// }
+    ///
+    /// let mut registry = Registry::new();
+    /// registry.register("text", initial_state);
+    /// assert_eq!(registry.lex_named("text", "luthor").unwrap()[0].lexeme, "luthor");
+    /// assert!(registry.lex_named("json", "{}").is_none());
+    /// ```
+    pub fn lex_named(&mut self, name: &str, data: &str) -> Option<Vec<Token<C>>> {
+        let initial_state = match *self.lexers.get_mut(name)? {
+            Lexer::State(StateFunction(ref mut initial_state)) => initial_state,
+            Lexer::Function(lex) => return Some(lex(data)),
+        };
+        let mut lexer = Tokenizer::new(data);
+
+        // The initial state is borrowed rather than consumed,
+        // so that it can be reused the next time it's needed.
+        if let Some(state_function) = initial_state(&mut lexer) {
+            lexer.run(state_function);
+        }
+
+        Some(lexer.into_tokens())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Registry;
+    use lexers::json;
+    use tokenizer::StateFunction;
+    use tokenizer::Tokenizer;
+    use token::Category;
+
+    fn words(lexer: &mut Tokenizer) -> Option<StateFunction> {
+        if lexer.current_char()?.is_whitespace() {
+            lexer.skip_whitespace();
+        } else {
+            lexer.consume_while(|c| !c.is_whitespace());
+            lexer.tokenize(Category::Identifier);
+        }
+
+        Some(StateFunction::new(words))
+    }
+
+    #[test]
+    fn lex_named_dispatches_to_the_registered_lexer() {
+        let mut registry = Registry::new();
+        registry.register("words", words);
+        registry.register("text", |lexer: &mut Tokenizer| {
+            lexer.tokenize_remaining(Category::Text);
+            None
+        });
+
+        for _ in 0..2 {
+            let tokens = registry.lex_named("words", "élé gant").unwrap();
+            let lexemes: Vec<&str> = tokens.iter().map(|token| &token.lexeme[..]).collect();
+            assert_eq!(lexemes, vec!["élé", " ", "gant"]);
+            assert_eq!(tokens[0].category, Category::Identifier);
+        }
+        assert_eq!(registry.lex_named("text", "élé gant").unwrap()[0].category, Category::Text);
+    }
+
+    #[test]
+    fn lex_named_dispatches_to_registered_built_in_lexers() {
+        let mut registry = Registry::new();
+        registry.register_fn("json", json::lex);
+        registry.register("words", words);
+
+        assert_eq!(registry.lex_named("json", "{\"a\": 1}").unwrap(), json::lex("{\"a\": 1}"));
+        assert_eq!(registry.lex_named("words", "{}").unwrap()[0].category, Category::Identifier);
+
+        registry.register_fn("words", json::lex);
+        assert_eq!(registry.lex_named("words", "{}").unwrap()[0].category, Category::Brace);
+    }
+
+    #[test]
+    fn lex_named_returns_none_for_unregistered_names() {
+        let mut registry = Registry::new();
+        registry.register("words", words);
+
+        assert!(registry.lex_named("Words", "élégant").is_none());
+        assert!(registry.lex_named("", "élégant").is_none());
+    }
+}
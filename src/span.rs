@@ -0,0 +1,22 @@
+/// The Span type describes the region of the source data covered by a
+/// token, in terms of the lines and columns at which it starts and ends.
+/// Lines and columns start at 1, and the end position is exclusive.
+#[derive(PartialEq, Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Span {
+    pub start_line: usize,
+    pub start_col: usize,
+    pub end_line: usize,
+    pub end_col: usize,
+}
+
+/// The Position type describes a single point in the source data, as
+/// both a character offset and the line and column at which it lies.
+/// The offset starts at 0, while lines and columns start at 1.
+#[derive(PartialEq, Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Position {
+    pub offset: usize,
+    pub line: usize,
+    pub column: usize,
+}
@@ -1,21 +1,324 @@
-#[derive(PartialEq, Debug, Clone)]
+use std::fmt;
+use std::ops::Range;
+use super::span::Span;
+
+#[derive(PartialEq, Eq, Hash, Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum Category {
     Whitespace,
+    Indentation,
     Identifier,
+    Variable,
     Keyword,
+    Directive,
     Brace,
     Bracket,
     Parenthesis,
     AssignmentOperator,
+    Operator,
+    Punctuation,
     Integer,
     Float,
     String,
+    Regex,
     Boolean,
+    DateTime,
     Text,
+    Comment,
+    Tag,
+    Attribute,
+    Selector,
+    Property,
+    Heading,
+    Emphasis,
+    Code,
+    Link,
+    ListMarker,
+    Addition,
+    Deletion,
+    // Custom names are static, so they can be serialized but not deserialized.
+    #[cfg_attr(feature = "serde", serde(skip_deserializing))]
+    Custom(#[cfg_attr(feature = "serde", serde(skip_deserializing))] &'static str),
+}
+
+impl fmt::Display for Category {
+    /// Formats the category using its variant name,
+    /// or its own name in the case of custom categories.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use luthor::token::Category;
+    ///
+    /// assert_eq!(Category::Keyword.to_string(), "Keyword");
+    /// assert_eq!(Category::Custom("directive").to_string(), "directive");
+    /// ```
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Category::Custom(name) => write!(f, "{}", name),
+            _ => write!(f, "{:?}", self),
+        }
+    }
 }
 
 #[derive(PartialEq, Debug, Clone)]
-pub struct Token {
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Token<C = Category> {
     pub lexeme: String,
-    pub category: Category,
+    pub category: C,
+    pub start: usize,
+    pub end: usize,
+    pub span: Span,
+}
+
+impl<C> Token<C> {
+    /// Returns the range of character offsets
+    /// in the source data covered by the token.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use luthor::token::Category;
+    ///
+    /// let mut lexer = luthor::tokenizer::new("luthor");
+    /// lexer.advance();
+    /// lexer.tokenize_next(5, Category::Text);
+    /// assert_eq!(lexer.tokens()[1].range(), 1..6);
+    /// ```
+    pub fn range(&self) -> Range<usize> {
+        self.start..self.end
+    }
+
+    /// Determines whether or not the lexeme
+    /// consists solely of whitespace characters.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use luthor::token::Category;
+    ///
+    /// let mut lexer = luthor::tokenizer::new(" \tluthor");
+    /// lexer.skip_whitespace();
+    /// lexer.tokenize_next(6, Category::Text);
+    /// assert!(lexer.tokens()[0].is_whitespace());
+    /// assert!(!lexer.tokens()[1].is_whitespace());
+    /// ```
+    pub fn is_whitespace(&self) -> bool {
+        !self.lexeme.is_empty() && self.lexeme.chars().all(char::is_whitespace)
+    }
+
+    /// Determines whether or not the lexeme is empty.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use luthor::token::Category;
+    ///
+    /// let mut lexer = luthor::tokenizer::new("luthor");
+    /// lexer.tokenize_next(6, Category::Text);
+    /// assert!(!lexer.tokens()[0].is_empty());
+    /// ```
+    pub fn is_empty(&self) -> bool {
+        self.lexeme.is_empty()
+    }
+
+    // Extends the token to cover the given token, which follows it.
+    pub(crate) fn merge(&mut self, token: &Token<C>) {
+        self.lexeme.push_str(&token.lexeme);
+        self.end = token.end;
+        self.span.end_line = token.span.end_line;
+        self.span.end_col = token.span.end_col;
+    }
+
+    /// Returns the number of characters in the lexeme.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use luthor::token::Category;
+    ///
+    /// let mut lexer = luthor::tokenizer::new("élégant");
+    /// lexer.tokenize_next(3, Category::Text);
+    /// assert_eq!(lexer.tokens()[0].len(), 3);
+    /// ```
+    pub fn len(&self) -> usize {
+        self.lexeme.chars().count()
+    }
+
+    /// Returns the number of bytes in the lexeme,
+    /// which exceeds its length if it isn't ASCII.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use luthor::token::Category;
+    ///
+    /// let mut lexer = luthor::tokenizer::new("élégant");
+    /// lexer.tokenize_next(3, Category::Text);
+    /// assert_eq!(lexer.tokens()[0].byte_len(), 5);
+    /// ```
+    pub fn byte_len(&self) -> usize {
+        self.lexeme.len()
+    }
+}
+
+impl<C: fmt::Display> fmt::Display for Token<C> {
+    /// Formats the token as its category followed by its quoted lexeme.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use luthor::token::Category;
+    ///
+    /// let mut lexer = luthor::tokenizer::new("luthor");
+    /// lexer.advance();
+    /// lexer.tokenize_next(5, Category::Keyword);
+    /// assert_eq!(lexer.tokens()[1].to_string(), "Keyword(\"uthor\")");
+    /// ```
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}({:?})", self.category, self.lexeme)
+    }
+}
+
+/// Merges consecutive tokens with the same category into single
+/// tokens, concatenating their lexemes and extending their ranges.
+///
+/// # Examples
+///
+/// ```
+/// use luthor::token::{merge_adjacent, Category};
+///
+/// let mut lexer = luthor::tokenizer::new("luthor");
+/// lexer.tokenize_next(2, Category::Text);
+/// lexer.tokenize_next(4, Category::Text);
+/// let tokens = merge_adjacent(lexer.into_tokens());
+/// assert_eq!(tokens.len(), 1);
+/// assert_eq!(tokens[0].lexeme, "luthor");
+/// ```
+pub fn merge_adjacent<C: PartialEq>(tokens: Vec<Token<C>>) -> Vec<Token<C>> {
+    let mut merged_tokens: Vec<Token<C>> = Vec::with_capacity(tokens.len());
+
+    for token in tokens {
+        match merged_tokens.last_mut() {
+            Some(previous) if previous.category == token.category => previous.merge(&token),
+            _ => merged_tokens.push(token),
+        }
+    }
+
+    merged_tokens
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Category;
+    use super::Token;
+    use super::merge_adjacent;
+    use super::super::span::Span;
+
+    #[test]
+    fn category_display_uses_the_variant_name() {
+        assert_eq!(format!("{}", Category::Keyword), "Keyword");
+        assert_eq!(format!("{}", Category::AssignmentOperator), "AssignmentOperator");
+        assert_eq!(format!("{}", Category::Custom("sql-hint")), "sql-hint");
+    }
+
+    #[test]
+    fn token_display_includes_the_category_and_quoted_lexeme() {
+        let token = Token{
+            lexeme: "say \"hi\"".to_string(),
+            category: Category::String,
+            start: 0,
+            end: 8,
+            span: Span{ start_line: 1, start_col: 1, end_line: 1, end_col: 9 },
+        };
+
+        assert_eq!(format!("{}", token), "String(\"say \\\"hi\\\"\")");
+    }
+
+    fn token(lexeme: &str, category: Category) -> Token {
+        let length = lexeme.chars().count();
+
+        Token{
+            lexeme: lexeme.to_string(),
+            category,
+            start: 0,
+            end: length,
+            span: Span{ start_line: 1, start_col: 1, end_line: 1, end_col: length + 1 },
+        }
+    }
+
+    #[test]
+    fn is_whitespace_is_true_for_whitespace_only_lexemes() {
+        assert!(token(" \t\r\n\u{a0}", Category::Whitespace).is_whitespace());
+        assert!(!token(" luthor ", Category::Text).is_whitespace());
+        assert!(!token("", Category::Whitespace).is_whitespace());
+    }
+
+    #[test]
+    fn is_empty_is_true_for_empty_lexemes() {
+        assert!(token("", Category::Text).is_empty());
+        assert!(!token(" ", Category::Whitespace).is_empty());
+    }
+
+    #[test]
+    fn len_counts_characters_rather_than_bytes() {
+        let accented_token = token("é", Category::Text);
+        assert_eq!(accented_token.len(), 1);
+        assert_eq!(accented_token.byte_len(), 2);
+        assert_eq!(token("", Category::Text).len(), 0);
+    }
+
+    #[test]
+    fn merge_adjacent_merges_tokens_with_the_same_category() {
+        let mut lexer = ::tokenizer::new("ab\nc fn d");
+        lexer.tokenize_next(2, Category::Text);
+        lexer.tokenize_next(1, Category::Text);
+        lexer.tokenize_next(2, Category::Text);
+        lexer.tokenize_next(2, Category::Keyword);
+        lexer.tokenize_next(2, Category::Text);
+        let tokens = merge_adjacent(lexer.into_tokens());
+
+        let expected_tokens = vec![
+            Token{
+                lexeme: "ab\nc ".to_string(),
+                category: Category::Text,
+                start: 0,
+                end: 5,
+                span: Span{ start_line: 1, start_col: 1, end_line: 2, end_col: 3 },
+            },
+            Token{
+                lexeme: "fn".to_string(),
+                category: Category::Keyword,
+                start: 5,
+                end: 7,
+                span: Span{ start_line: 2, start_col: 3, end_line: 2, end_col: 5 },
+            },
+            Token{
+                lexeme: " d".to_string(),
+                category: Category::Text,
+                start: 7,
+                end: 9,
+                span: Span{ start_line: 2, start_col: 5, end_line: 2, end_col: 7 },
+            },
+        ];
+        assert_eq!(tokens, expected_tokens);
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn tokens_can_be_serialized_and_deserialized() {
+        use serde_json;
+
+        let mut lexer = ::tokenizer::new("fn luthor");
+        lexer.tokenize_next(2, Category::Keyword);
+        lexer.skip_whitespace();
+        lexer.tokenize_next(6, Category::Identifier);
+        let tokens = lexer.tokens();
+
+        let json = serde_json::to_string(&tokens).unwrap();
+        assert!(json.starts_with("[{\"lexeme\":\"fn\",\"category\":\"Keyword\","));
+
+        let deserialized_tokens: Vec<Token> = serde_json::from_str(&json).unwrap();
+        assert_eq!(deserialized_tokens, tokens);
+    }
 }
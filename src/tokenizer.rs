@@ -1,17 +1,82 @@
 use std::cmp::min;
+use std::collections::HashMap;
+use std::hash::Hash;
+use std::io;
+use std::io::Read;
+use std::mem;
+use std::ops::Range;
 use super::token::Token;
 use super::token::Category;
+use super::span::Span;
+use super::span::Position;
+use super::error::LexError;
+#[cfg(feature = "unicode-segmentation")]
+use unicode_segmentation::UnicodeSegmentation;
 
-pub struct StateFunction(pub fn(&mut Tokenizer) -> Option<StateFunction>);
+/// The StateFunction type wraps a single state of a lexer, which
+/// processes some data and returns the next state, if there is one.
+/// Any function or closure with the right signature can be used,
+/// so states can capture configuration such as a keyword set.
+pub struct StateFunction<C = Category>(pub Box<StateFn<C>>);
+
+type StateFn<C> = dyn FnMut(&mut Tokenizer<C>) -> Option<StateFunction<C>>;
+
+impl<C> StateFunction<C> {
+    /// Wraps the given function or closure as a state function.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use luthor::tokenizer::{StateFunction, Tokenizer};
+    ///
+    /// fn initial_state(lexer: &mut Tokenizer) -> Option<StateFunction> {
+    ///     None
+    /// }
+    ///
+    /// let state_function = StateFunction::new(initial_state);
+    /// ```
+    pub fn new<F>(function: F) -> StateFunction<C>
+        where F: FnMut(&mut Tokenizer<C>) -> Option<StateFunction<C>> + 'static {
+        StateFunction(Box::new(function))
+    }
+}
 
 /// The Tokenizer type is used to produce and store
 /// tokens for the various language and format lexers.
-pub struct Tokenizer {
+/// Tokens are categorized using the Category type by
+/// default, but any category type can be used instead.
+///
+/// The data and cursor fields are public, so methods tolerate cursors
+/// set out of range rather than panicking. The data should be replaced
+/// using `reset`, though, which recomputes its character offsets.
+pub struct Tokenizer<C = Category> {
     pub data: String,
     char_count: usize,
+    char_offsets: Vec<usize>,
+    pub token_start: usize,
+    pub token_position: usize,
+    line: usize,
+    column: usize,
+    start_line: usize,
+    start_column: usize,
+    tokens: Vec<Token<C>>,
+    errors: Vec<LexError>,
+    #[cfg(feature = "unicode-segmentation")]
+    graphemes: bool,
+}
+
+/// The Checkpoint type captures the cursor and token count of a
+/// tokenizer, so that it can later be restored to that point.
+#[derive(PartialEq, Debug, Clone, Copy)]
+pub struct Checkpoint {
     pub token_start: usize,
     pub token_position: usize,
-    tokens: Vec<Token>,
+    pub token_count: usize,
+    error_count: usize,
+    line: usize,
+    column: usize,
+    start_line: usize,
+    start_column: usize,
 }
 
 /// Initializes a new tokenizer with the given data.
@@ -22,16 +87,175 @@ pub struct Tokenizer {
 /// let lexer = luthor::tokenizer::new("luthor");
 /// ```
 pub fn new(data: &str) -> Tokenizer {
-    Tokenizer{
-      data: data.to_string(),
-      char_count: data.chars().count(),
-      token_start: 0,
-      token_position: 0,
-      tokens: vec![]
-    }
+    Tokenizer::new(data)
 }
 
-impl Tokenizer {
+/// Initializes a new tokenizer with the given data, with space
+/// reserved for at least `capacity` tokens.
+///
+/// # Examples
+///
+/// ```
+/// let lexer = luthor::tokenizer::with_capacity("luthor", 6);
+/// ```
+pub fn with_capacity(data: &str, capacity: usize) -> Tokenizer {
+    Tokenizer::with_capacity(data, capacity)
+}
+
+/// Initializes a new tokenizer with the given data, preceding the
+/// tokens it processes with the given ones (e.g. those of another lexer).
+///
+/// # Examples
+///
+/// ```
+/// use luthor::token::Category;
+///
+/// let mut heading = luthor::tokenizer::new("# ");
+/// heading.tokenize_next(2, Category::Heading);
+///
+/// let mut lexer = luthor::tokenizer::with_tokens("luthor", heading.into_tokens());
+/// lexer.tokenize_next(6, Category::Text);
+/// assert_eq!(lexer.tokens()[0].lexeme, "# ");
+/// assert_eq!(lexer.tokens()[1].lexeme, "luthor");
+/// ```
+pub fn with_tokens(data: &str, tokens: Vec<Token>) -> Tokenizer {
+    Tokenizer::with_tokens(data, tokens)
+}
+
+/// Initializes a new tokenizer with the data read from the given
+/// reader, which is read in its entirety and must be valid UTF-8.
+///
+/// # Examples
+///
+/// ```
+/// use std::io::Cursor;
+///
+/// let lexer = luthor::tokenizer::from_reader(Cursor::new("luthor")).unwrap();
+/// assert_eq!(lexer.data, "luthor");
+/// ```
+pub fn from_reader<R: Read>(reader: R) -> io::Result<Tokenizer> {
+    Tokenizer::from_reader(reader)
+}
+
+impl<C> Tokenizer<C> {
+    /// Initializes a new tokenizer with the given data,
+    /// using the given category type for its tokens.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use luthor::tokenizer::Tokenizer;
+    ///
+    /// #[derive(PartialEq, Debug, Clone)]
+    /// enum Markup { Tag, Text }
+    ///
+    /// let lexer: Tokenizer<Markup> = Tokenizer::new("<b>luthor</b>");
+    /// ```
+    pub fn new(data: &str) -> Tokenizer<C> {
+        let mut tokenizer = Tokenizer{
+          data: data.to_string(),
+          char_count: 0,
+          char_offsets: Vec::with_capacity(data.len()),
+          token_start: 0,
+          token_position: 0,
+          line: 1,
+          column: 1,
+          start_line: 1,
+          start_column: 1,
+          tokens: vec![],
+          errors: vec![],
+          #[cfg(feature = "unicode-segmentation")]
+          graphemes: false,
+        };
+        tokenizer.cache_offsets();
+        tokenizer
+    }
+
+    /// Initializes a new tokenizer with the given data that processes it
+    /// in extended grapheme clusters rather than characters, so that
+    /// combining sequences and emoji are advanced over as single units.
+    /// Positions, spans and amounts are all counted in grapheme clusters,
+    /// and `current_char` returns the first character of the current one.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use luthor::tokenizer::Tokenizer;
+    ///
+    /// let mut lexer: Tokenizer = Tokenizer::with_graphemes("e\u{301}t\u{e9}");
+    /// lexer.advance();
+    /// assert_eq!(lexer.current_lexeme(), "e\u{301}");
+    /// assert_eq!(lexer.current_char().unwrap(), 't');
+    /// ```
+    #[cfg(feature = "unicode-segmentation")]
+    pub fn with_graphemes(data: &str) -> Tokenizer<C> {
+        let mut tokenizer = Tokenizer::new("");
+        tokenizer.graphemes = true;
+        tokenizer.reset(data);
+        tokenizer
+    }
+
+    /// Initializes a new tokenizer with the given data, using the given
+    /// category type for its tokens, with space reserved for at least
+    /// `capacity` of them.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use luthor::tokenizer::Tokenizer;
+    ///
+    /// #[derive(PartialEq, Debug, Clone)]
+    /// enum Markup { Tag, Text }
+    ///
+    /// let lexer: Tokenizer<Markup> = Tokenizer::with_capacity("<b>", 1);
+    /// ```
+    pub fn with_capacity(data: &str, capacity: usize) -> Tokenizer<C> {
+        let mut tokenizer = Tokenizer::new(data);
+        tokenizer.tokens.reserve_exact(capacity);
+        tokenizer
+    }
+
+    /// Initializes a new tokenizer with the given data, preceding the
+    /// tokens it processes with the given ones, which share their category
+    /// type. The cursor starts at the beginning of the data regardless.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use luthor::tokenizer::Tokenizer;
+    ///
+    /// #[derive(PartialEq, Debug, Clone)]
+    /// enum Markup { Tag, Text }
+    ///
+    /// let lexer: Tokenizer<Markup> = Tokenizer::with_tokens("<b>", Vec::new());
+    /// ```
+    pub fn with_tokens(data: &str, tokens: Vec<Token<C>>) -> Tokenizer<C> {
+        let mut tokenizer = Tokenizer::new(data);
+        tokenizer.tokens = tokens;
+        tokenizer
+    }
+
+    /// Initializes a new tokenizer with the data read from the given
+    /// reader, using the given category type for its tokens.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::io::Cursor;
+    /// use luthor::tokenizer::Tokenizer;
+    ///
+    /// #[derive(PartialEq, Debug, Clone)]
+    /// enum Markup { Tag, Text }
+    ///
+    /// let lexer: Tokenizer<Markup> = Tokenizer::from_reader(Cursor::new("<b>")).unwrap();
+    /// ```
+    pub fn from_reader<R: Read>(mut reader: R) -> io::Result<Tokenizer<C>> {
+        let mut data = String::new();
+        reader.read_to_string(&mut data)?;
+
+        Ok(Tokenizer::new(&data))
+    }
+
     /// Returns a copy of the tokens processed to date.
     ///
     /// # Examples
@@ -40,248 +264,3215 @@ impl Tokenizer {
     /// let lexer = luthor::tokenizer::new("luthor");
     /// lexer.tokens();
     /// ```
-    pub fn tokens(&self) -> Vec<Token> {
+    pub fn tokens(&self) -> Vec<Token<C>> where C: Clone {
         self.tokens.clone()
     }
 
-    /// Moves to the next character in the data.
-    /// Does nothing if there is no more data to process.
+    /// Returns a reference to the tokens processed to date.
     ///
     /// # Examples
     ///
     /// ```
-    /// let mut lexer = luthor::tokenizer::new("luthor");
-    /// assert_eq!(lexer.current_char().unwrap(), 'l');
-    /// lexer.advance();
-    /// assert_eq!(lexer.current_char().unwrap(), 'u');
+    /// let lexer = luthor::tokenizer::new("luthor");
+    /// assert!(lexer.tokens_ref().is_empty());
     /// ```
-    pub fn advance(&mut self) {
-        if self.has_more_data() {
-            self.token_position += 1;
-        }
+    pub fn tokens_ref(&self) -> &[Token<C>] {
+        &self.tokens
     }
 
-    /// Determines whether or not there is more unprocessed data.
+    /// Returns a copy of the tokens processed to date, each paired
+    /// with the range of character offsets it covers in the data.
     ///
     /// # Examples
     ///
     /// ```
-    /// let mut lexer = luthor::tokenizer::new("l");
-    /// assert_eq!(lexer.has_more_data(), true);
-    /// lexer.advance();
-    /// assert_eq!(lexer.has_more_data(), false);
+    /// use luthor::token::Category;
+    ///
+    /// let mut lexer = luthor::tokenizer::new("fn luthor");
+    /// lexer.tokenize_next(2, Category::Keyword);
+    /// lexer.skip_whitespace();
+    /// let (ref token, ref range) = lexer.tokens_with_spans()[1];
+    /// assert_eq!(token.lexeme, " ");
+    /// assert_eq!(*range, 2..3);
     /// ```
-    pub fn has_more_data(&self) -> bool {
-        self.token_position < self.char_count
+    pub fn tokens_with_spans(&self) -> Vec<(Token<C>, Range<usize>)> where C: Clone {
+        self.tokens.iter().map(|token| (token.clone(), token.range())).collect()
     }
 
-    /// Returns the character at the current position,
-    /// unless all of the data has been processed.
+    /// Returns the number of tokens processed to date.
     ///
     /// # Examples
     ///
     /// ```
-    /// let mut lexer = luthor::tokenizer::new("l");
-    /// assert_eq!(lexer.current_char().unwrap(), 'l');
-    /// lexer.advance();
-    /// assert_eq!(lexer.current_char(), None);
+    /// use luthor::token::Category;
+    ///
+    /// let mut lexer = luthor::tokenizer::new("luthor");
+    /// lexer.tokenize_next(6, Category::Text);
+    /// assert_eq!(lexer.token_count(), 1);
     /// ```
-    pub fn current_char(&self) -> Option<char> {
-        if self.has_more_data() {
-            Some(self.data.chars().nth(self.token_position).unwrap())
-        } else {
-            None
-        }
+    pub fn token_count(&self) -> usize {
+        self.tokens.len()
     }
 
-    /// Creates and stores a token with the given category containing any
-    /// data processed using `advance` since the last call to this method.
+    /// Determines whether or not any tokens have been processed to date.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let lexer = luthor::tokenizer::new("luthor");
+    /// assert!(lexer.is_empty());
+    /// ```
+    pub fn is_empty(&self) -> bool {
+        self.tokens.is_empty()
+    }
+
+    /// Returns a reference to the most recently processed token, if any,
+    /// for state functions that depend on the preceding context.
     ///
     /// # Examples
     ///
     /// ```
     /// use luthor::token::Category;
+    ///
     /// let mut lexer = luthor::tokenizer::new("luthor");
-    /// lexer.advance();
-    /// lexer.advance();
-    /// lexer.tokenize(Category::Text);
-    /// assert_eq!(lexer.tokens()[0].lexeme, "lu");
+    /// assert!(lexer.last_token().is_none());
+    /// lexer.tokenize_next(6, Category::Text);
+    /// assert_eq!(lexer.last_token().unwrap().lexeme, "luthor");
     /// ```
-    pub fn tokenize(&mut self, category: Category) {
-        if self.token_start != self.token_position {
-            let token = Token{
-                lexeme: self.data.slice_chars(self.token_start, self.token_position).to_string(),
-                category: category,
-            };
-            self.tokens.push(token);
-            self.token_start = self.token_position;
-        }
+    pub fn last_token(&self) -> Option<&Token<C>> {
+        self.tokens.last()
     }
 
-    /// Creates and stores a token with the given category and the
-    /// next `amount` characters of the data. Before doing this, it
-    /// tokenizes any previously processed characters with the generic
-    /// Category::Text category.
+    /// Replaces the category of the processed token at the given index,
+    /// for lexers that refine categories in a later pass. Returns false
+    /// if there is no token at the index.
     ///
     /// # Examples
     ///
     /// ```
     /// use luthor::token::Category;
-    /// use luthor::token::Token;
     ///
     /// let mut lexer = luthor::tokenizer::new("luthor");
-    /// lexer.advance();
-    /// lexer.tokenize_next(5, Category::Keyword);
-    /// assert_eq!(lexer.tokens()[0], Token{ lexeme: "l".to_string(), category: Category::Text});
-    /// assert_eq!(lexer.tokens()[1], Token{ lexeme: "uthor".to_string(), category: Category::Keyword});
+    /// lexer.tokenize_next(6, Category::Text);
+    /// assert!(lexer.set_category(0, Category::Keyword));
+    /// assert_eq!(lexer.tokens()[0].category, Category::Keyword);
     /// ```
-    pub fn tokenize_next(&mut self, amount: usize, category: Category) {
-        self.tokenize(Category::Text);
-        self.token_position = min(self.token_position + amount, self.char_count);
-        self.tokenize(category);
+    pub fn set_category(&mut self, index: usize, category: C) -> bool {
+        match self.tokens.get_mut(index) {
+            Some(token) => {
+                token.category = category;
+                true
+            },
+            None => false,
+        }
     }
-}
-
-mod tests {
-    use super::new;
-    use super::super::token::Token;
-    use super::super::token::Category;
 
-    #[test]
-    fn new_initializes_correctly_with_unicode_data() {
-        let lexer_data = "différent";
-        let lexer = new(lexer_data);
-        assert_eq!(lexer.data, lexer_data);
-        assert_eq!(lexer.char_count, 9);
-        assert_eq!(lexer.token_start, 0);
-        assert_eq!(lexer.token_position, 0);
-        assert_eq!(lexer.tokens, vec![]);
+    /// Returns a reference to the processed token covering the given
+    /// character offset, if any, found using a binary search.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use luthor::token::Category;
+    ///
+    /// let mut lexer = luthor::tokenizer::new("fn luthor");
+    /// lexer.tokenize_next(2, Category::Keyword);
+    /// lexer.skip_whitespace();
+    /// lexer.tokenize_next(6, Category::Identifier);
+    /// assert_eq!(lexer.token_at_offset(5).unwrap().lexeme, "luthor");
+    /// assert!(lexer.token_at_offset(9).is_none());
+    /// ```
+    pub fn token_at_offset(&self, offset: usize) -> Option<&Token<C>> {
+        let index = self.tokens.partition_point(|token| token.end <= offset);
+        self.tokens.get(index).filter(|token| token.start <= offset)
     }
 
-    #[test]
-    fn advance_increments_the_cursor_by_one() {
-        let lexer_data = "élégant";
-        let mut lexer = new(lexer_data);
-        lexer.advance();
-        assert_eq!(lexer.token_position, 1);
-        lexer.advance();
-        assert_eq!(lexer.token_position, 2);
+    /// Returns a copy of the tokens processed to date
+    /// that have the given category, in source order.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use luthor::token::Category;
+    ///
+    /// let mut lexer = luthor::tokenizer::new("fn luthor");
+    /// lexer.tokenize_next(2, Category::Keyword);
+    /// lexer.skip_whitespace();
+    /// assert_eq!(lexer.tokens_of(Category::Keyword)[0].lexeme, "fn");
+    /// ```
+    pub fn tokens_of(&self, category: C) -> Vec<Token<C>> where C: Clone + PartialEq {
+        self.tokens.iter().filter(|token| token.category == category).cloned().collect()
     }
 
-    #[test]
-    fn advance_stops_when_there_is_no_more_data() {
-        let lexer_data = "élégant";
-        let mut lexer = new(lexer_data);
+    /// Determines whether or not any of the tokens
+    /// processed to date have the given category.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use luthor::token::Category;
+    ///
+    /// let mut lexer = luthor::tokenizer::new("fn luthor");
+    /// lexer.tokenize_next(2, Category::Keyword);
+    /// assert!(lexer.contains_category(Category::Keyword));
+    /// assert!(!lexer.contains_category(Category::Comment));
+    /// ```
+    pub fn contains_category(&self, category: C) -> bool where C: PartialEq {
+        self.tokens.iter().any(|token| token.category == category)
+    }
 
-        // Try to go beyond the last character.
-        for _ in 0..lexer.char_count {
-            lexer.advance();
+    /// Returns the number of tokens processed to date in each category.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use luthor::token::Category;
+    ///
+    /// let mut lexer = luthor::tokenizer::new("fn luthor");
+    /// lexer.tokenize_next(2, Category::Keyword);
+    /// lexer.skip_whitespace();
+    /// assert_eq!(lexer.category_counts()[&Category::Keyword], 1);
+    /// ```
+    pub fn category_counts(&self) -> HashMap<C, usize> where C: Clone + Eq + Hash {
+        let mut counts = HashMap::new();
+        for token in &self.tokens {
+            *counts.entry(token.category.clone()).or_insert(0) += 1;
         }
 
-        assert_eq!(lexer.token_position, lexer.char_count);
+        counts
     }
 
-    #[test]
-    fn has_more_data_works() {
-        let lexer_data = "él";
-        let mut lexer = new(lexer_data);
-
-        lexer.advance();
-        assert!(lexer.has_more_data());
-
-        lexer.advance();
-        assert_eq!(lexer.has_more_data(), false);
+    /// Consumes the tokenizer, returning the tokens
+    /// processed to date without copying them.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let lexer = luthor::tokenizer::new("luthor");
+    /// let tokens = lexer.into_tokens();
+    /// ```
+    pub fn into_tokens(self) -> Vec<Token<C>> {
+        self.tokens
     }
 
-    #[test]
-    fn current_char_returns_the_char_at_token_position() {
-        let lexer_data = "él";
-        let mut lexer = new(lexer_data);
-
-        assert_eq!(lexer.current_char().unwrap(), 'é');
+    /// Returns the lexemes of the tokens processed to date, joined
+    /// in order. Once all of the data has been tokenized, this is
+    /// identical to the data for lexers that don't discard any of it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use luthor::token::Category;
+    ///
+    /// let mut lexer = luthor::tokenizer::new("luthor");
+    /// lexer.advance();
+    /// lexer.tokenize_next(5, Category::Keyword);
+    /// assert_eq!(lexer.reconstruct(), "luthor");
+    /// ```
+    pub fn reconstruct(&self) -> String {
+        self.tokens.iter().map(|token| &token.lexeme[..]).collect()
     }
 
-    #[test]
-    fn current_char_returns_none_if_at_the_end() {
-        let lexer_data = "él";
-        let mut lexer = new(lexer_data);
-        lexer.advance();
-        lexer.advance();
-
-        assert_eq!(lexer.current_char(), None);
+    /// Replaces the data and discards all tokens processed to date,
+    /// returning the tokenizer to its initial state. Previously
+    /// allocated buffers are reused rather than reallocated, and
+    /// the data is processed in the same units as before.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use luthor::token::Category;
+    ///
+    /// let mut lexer = luthor::tokenizer::new("luthor");
+    /// lexer.tokenize_next(6, Category::Text);
+    /// lexer.reset("lexer");
+    /// assert_eq!(lexer.tokens().len(), 0);
+    /// assert_eq!(lexer.current_char().unwrap(), 'l');
+    /// ```
+    pub fn reset(&mut self, data: &str) {
+        self.data.clear();
+        self.data.push_str(data);
+        self.cache_offsets();
+        self.token_start = 0;
+        self.token_position = 0;
+        self.line = 1;
+        self.column = 1;
+        self.start_line = 1;
+        self.start_column = 1;
+        self.tokens.clear();
+        self.errors.clear();
     }
 
-    #[test]
+    /// Moves to the next character in the data.
+    /// Does nothing if there is no more data to process.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let mut lexer = luthor::tokenizer::new("luthor");
+    /// assert_eq!(lexer.current_char().unwrap(), 'l');
+    /// lexer.advance();
+    /// assert_eq!(lexer.current_char().unwrap(), 'u');
+    /// ```
+    pub fn advance(&mut self) {
+        if let Some(unit) = self.current_unit() {
+            // Grapheme clusters end with the newline in a CRLF sequence.
+            if unit.ends_with('\n') {
+                self.line += 1;
+                self.column = 1;
+            } else {
+                self.column += 1;
+            }
+            self.token_position += 1;
+        }
+    }
+
+    /// Moves forward by up to `amount` characters in the data,
+    /// stopping at the end if there is not enough data to process.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let mut lexer = luthor::tokenizer::new("luthor");
+    /// lexer.advance_by(3);
+    /// assert_eq!(lexer.current_char().unwrap(), 'h');
+    /// ```
+    pub fn advance_by(&mut self, amount: usize) {
+        for _ in 0..min(amount, self.remaining_len()) {
+            self.advance();
+        }
+    }
+
+    /// Moves the cursor to the given character position, clamped to the
+    /// end of the data, and starts the next token there. Any data processed
+    /// since the last token was created is discarded, as with `ignore`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use luthor::token::Category;
+    ///
+    /// let mut lexer = luthor::tokenizer::new("luthor");
+    /// lexer.advance();
+    /// lexer.seek(3);
+    /// lexer.tokenize_next(3, Category::Text);
+    /// assert_eq!(lexer.tokens()[0].lexeme, "hor");
+    /// ```
+    pub fn seek(&mut self, position: usize) {
+        self.move_to(min(position, self.char_count));
+        self.ignore();
+    }
+
+    /// Moves back to the previous character in the data.
+    /// Does nothing if the cursor is already at the start of the
+    /// current token, so tokenized data can't be revisited.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let mut lexer = luthor::tokenizer::new("luthor");
+    /// lexer.advance();
+    /// lexer.advance();
+    /// lexer.backup();
+    /// assert_eq!(lexer.current_char().unwrap(), 'u');
+    /// ```
+    pub fn backup(&mut self) {
+        if self.token_position > self.token_start {
+            self.token_position -= 1;
+
+            if self.current_unit().is_some_and(|unit| unit.ends_with('\n')) {
+                // The column on the previous line is only known
+                // by counting back to the newline preceding it.
+                let line = self.slice(0, self.token_position).rsplit('\n').next().unwrap();
+                self.column = self.unit_count(line) + 1;
+                self.line = self.line.saturating_sub(1).max(1);
+            } else {
+                self.column = self.column.saturating_sub(1).max(1);
+            }
+        }
+    }
+
+    /// Moves forward in the data for as long as the character at
+    /// the current position satisfies the given predicate.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let mut lexer = luthor::tokenizer::new("123abc");
+    /// lexer.consume_while(|c| c.is_numeric());
+    /// assert_eq!(lexer.current_char().unwrap(), 'a');
+    /// ```
+    pub fn consume_while<F: FnMut(char) -> bool>(&mut self, mut predicate: F) {
+        while let Some(c) = self.current_char() {
+            if !predicate(c) {
+                break;
+            }
+            self.advance();
+        }
+    }
+
+    /// Moves forward in the data until the character at the current
+    /// position satisfies the given predicate, leaving it unprocessed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let mut lexer = luthor::tokenizer::new("luthor\"");
+    /// lexer.consume_until(|c| c == '"');
+    /// assert_eq!(lexer.current_char().unwrap(), '"');
+    /// ```
+    pub fn consume_until<F: FnMut(char) -> bool>(&mut self, mut predicate: F) {
+        self.consume_while(|c| !predicate(c));
+    }
+
+    /// Moves forward until the current character is the target, without
+    /// consuming it, or until there is no more data to process. Returns
+    /// whether or not the target was found.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let mut lexer = luthor::tokenizer::new("\"luthor\"");
+    /// lexer.advance();
+    /// assert!(lexer.advance_to('"'));
+    /// assert_eq!(lexer.current_lexeme(), "\"luthor");
+    /// ```
+    pub fn advance_to(&mut self, target: char) -> bool {
+        self.consume_until(|c| c == target);
+        self.current_char() == Some(target)
+    }
+
+    /// Moves forward over any consecutive occurrences of
+    /// the target character, returning how many there were.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let mut lexer = luthor::tokenizer::new("## luthor");
+    /// assert_eq!(lexer.advance_while_char('#'), 2);
+    /// assert_eq!(lexer.current_char().unwrap(), ' ');
+    /// ```
+    pub fn advance_while_char(&mut self, target: char) -> usize {
+        let start = self.token_position;
+        self.consume_while(|c| c == target);
+        self.token_position - start
+    }
+
+    /// Moves forward past the next newline, or to the end of
+    /// the data if there isn't one, skipping the rest of the line.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let mut lexer = luthor::tokenizer::new("lu\nthor");
+    /// lexer.advance_line();
+    /// assert_eq!(lexer.current_lexeme(), "lu\n");
+    /// assert_eq!(lexer.position(), (2, 1));
+    /// ```
+    pub fn advance_line(&mut self) {
+        self.advance_to('\n');
+        self.advance();
+    }
+
+    /// Moves forward from an opening delimiter at the current position
+    /// to just past its matching closing delimiter, accounting for any
+    /// nested pairs. Returns whether or not a matching closing delimiter
+    /// was found; if not, the cursor is left at the end of the data.
+    /// Nothing happens if the current character isn't the opening delimiter.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let mut lexer = luthor::tokenizer::new("(a(b)c) d");
+    /// assert!(lexer.consume_balanced('(', ')'));
+    /// assert_eq!(lexer.current_lexeme(), "(a(b)c)");
+    /// ```
+    pub fn consume_balanced(&mut self, open: char, close: char) -> bool {
+        if self.current_char() != Some(open) {
+            return false;
+        }
+
+        let mut depth = 0;
+        while let Some(c) = self.current_char() {
+            self.advance();
+            if c == close {
+                depth -= 1;
+                if depth == 0 {
+                    return true;
+                }
+            } else if c == open {
+                depth += 1;
+            }
+        }
+
+        false
+    }
+
+    /// Moves to the next character in the data if the character
+    /// at the current position is one of the valid characters.
+    /// Returns whether or not it moved.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let mut lexer = luthor::tokenizer::new("-42");
+    /// assert_eq!(lexer.accept("+-"), true);
+    /// assert_eq!(lexer.accept("+-"), false);
+    /// assert_eq!(lexer.current_char().unwrap(), '4');
+    /// ```
+    pub fn accept(&mut self, valid: &str) -> bool {
+        match self.current_char() {
+            Some(c) if valid.contains(c) => {
+                self.advance();
+                true
+            },
+            _ => false,
+        }
+    }
+
+    /// Moves forward in the data for as long as the character
+    /// at the current position is one of the valid characters.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let mut lexer = luthor::tokenizer::new("-42;");
+    /// lexer.accept("+-");
+    /// lexer.accept_run("0123456789");
+    /// assert_eq!(lexer.current_char().unwrap(), ';');
+    /// ```
+    pub fn accept_run(&mut self, valid: &str) {
+        self.consume_while(|c| valid.contains(c));
+    }
+
+    /// Determines whether or not there is more unprocessed data.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let mut lexer = luthor::tokenizer::new("l");
+    /// assert_eq!(lexer.has_more_data(), true);
+    /// lexer.advance();
+    /// assert_eq!(lexer.has_more_data(), false);
+    /// ```
+    pub fn has_more_data(&self) -> bool {
+        self.token_position < self.char_count
+    }
+
+    /// Returns the number of characters in the data.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let lexer = luthor::tokenizer::new("élégant");
+    /// assert_eq!(lexer.data_len(), 7);
+    /// ```
+    pub fn data_len(&self) -> usize {
+        self.char_count
+    }
+
+    /// Returns the number of characters that have yet to be processed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let mut lexer = luthor::tokenizer::new("luthor");
+    /// lexer.advance_by(2);
+    /// assert_eq!(lexer.remaining_len(), 4);
+    /// ```
+    pub fn remaining_len(&self) -> usize {
+        self.char_count.saturating_sub(self.token_position)
+    }
+
+    /// Returns the line and column of the current position,
+    /// both of which start at 1.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let mut lexer = luthor::tokenizer::new("l\nuthor");
+    /// assert_eq!(lexer.position(), (1, 1));
+    /// lexer.advance_by(3);
+    /// assert_eq!(lexer.position(), (2, 2));
+    /// ```
+    pub fn position(&self) -> (usize, usize) {
+        (self.line, self.column)
+    }
+
+    /// Returns the character offset, line and column of the current position.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let mut lexer = luthor::tokenizer::new("l\nuthor");
+    /// lexer.advance_by(3);
+    /// let position = lexer.current_position();
+    /// assert_eq!((position.offset, position.line, position.column), (3, 2, 2));
+    /// ```
+    pub fn current_position(&self) -> Position {
+        Position{
+            offset: self.token_position,
+            line: self.line,
+            column: self.column,
+        }
+    }
+
+    /// Returns the byte offset of the current position in the data,
+    /// for consumers (e.g. editors) that work with byte offsets.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let mut lexer = luthor::tokenizer::new("éa");
+    /// lexer.advance();
+    /// assert_eq!(lexer.byte_offset(), 2);
+    /// ```
+    pub fn byte_offset(&self) -> usize {
+        self.offset_of(self.token_position)
+    }
+
+    /// Returns the character at the current position,
+    /// unless all of the data has been processed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let mut lexer = luthor::tokenizer::new("l");
+    /// assert_eq!(lexer.current_char().unwrap(), 'l');
+    /// lexer.advance();
+    /// assert_eq!(lexer.current_char(), None);
+    /// ```
+    pub fn current_char(&self) -> Option<char> {
+        self.char_at(self.token_position)
+    }
+
+    /// Returns the character following the one at the current position,
+    /// without advancing, unless there is no such character.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let lexer = luthor::tokenizer::new("luthor");
+    /// assert_eq!(lexer.current_char().unwrap(), 'l');
+    /// assert_eq!(lexer.peek_char().unwrap(), 'u');
+    /// ```
+    pub fn peek_char(&self) -> Option<char> {
+        self.peek(1)
+    }
+
+    /// Returns the character `offset` positions ahead of the current
+    /// one, without advancing, unless there is no such character.
+    /// A zero offset is equivalent to `current_char`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let lexer = luthor::tokenizer::new("luthor");
+    /// assert_eq!(lexer.peek(0).unwrap(), 'l');
+    /// assert_eq!(lexer.peek(5).unwrap(), 'r');
+    /// assert_eq!(lexer.peek(6), None);
+    /// ```
+    pub fn peek(&self, offset: usize) -> Option<char> {
+        self.char_at(self.token_position.saturating_add(offset))
+    }
+
+    /// Returns the character at the given position in the data,
+    /// regardless of the current one, unless there is no such character.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let mut lexer = luthor::tokenizer::new("luthor");
+    /// lexer.advance_by(3);
+    /// assert_eq!(lexer.char_at(1).unwrap(), 'u');
+    /// assert_eq!(lexer.char_at(6), None);
+    /// ```
+    pub fn char_at(&self, index: usize) -> Option<char> {
+        self.slice(index, self.char_count).chars().next()
+    }
+
+    /// Returns the data processed using `advance` since the last
+    /// token was created, which is what `tokenize` would store.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let mut lexer = luthor::tokenizer::new("luthor");
+    /// assert_eq!(lexer.current_lexeme(), "");
+    /// lexer.advance();
+    /// lexer.advance();
+    /// assert_eq!(lexer.current_lexeme(), "lu");
+    /// ```
+    pub fn current_lexeme(&self) -> &str {
+        self.slice(self.token_start, self.token_position)
+    }
+
+    /// Determines whether or not the data processed since the last
+    /// token was created exactly matches one of the given words.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let mut lexer = luthor::tokenizer::new("fn luthor");
+    /// lexer.advance_by(2);
+    /// assert!(lexer.current_is_one_of(&["fn", "let"]));
+    /// assert!(!lexer.current_is_one_of(&["f", "let"]));
+    /// ```
+    pub fn current_is_one_of(&self, words: &[&str]) -> bool {
+        words.contains(&self.current_lexeme())
+    }
+
+    /// Returns the number of characters processed using `advance`
+    /// since the last token was created, without allocating.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let mut lexer = luthor::tokenizer::new("luthor");
+    /// lexer.advance();
+    /// lexer.advance();
+    /// assert_eq!(lexer.pending_len(), 2);
+    /// ```
+    pub fn pending_len(&self) -> usize {
+        self.token_position.saturating_sub(self.token_start)
+    }
+
+    /// Returns the data that has yet to be processed,
+    /// starting with the character at the current position.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let mut lexer = luthor::tokenizer::new("luthor");
+    /// lexer.advance();
+    /// assert_eq!(lexer.remaining(), "uthor");
+    /// ```
+    pub fn remaining(&self) -> &str {
+        self.slice(self.token_position, self.char_count)
+    }
+
+    /// Determines whether or not the unprocessed data
+    /// begins with the given prefix.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let mut lexer = luthor::tokenizer::new("pub fn main() {}");
+    /// assert_eq!(lexer.starts_with("fn "), false);
+    /// lexer.advance_by(4);
+    /// assert_eq!(lexer.starts_with("fn "), true);
+    /// ```
+    pub fn starts_with(&self, prefix: &str) -> bool {
+        self.remaining().starts_with(prefix)
+    }
+
+    /// Determines whether or not the characters starting at the current
+    /// position satisfy the given predicates, in order, without advancing.
+    /// Predicates of differing types can be passed as function pointers.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let lexer = luthor::tokenizer::new("07:32");
+    /// let is_digit = |c: char| c.is_ascii_digit();
+    /// let time: [fn(char) -> bool; 3] = [is_digit, is_digit, |c| c == ':'];
+    /// assert!(lexer.peek_matches(&time));
+    /// ```
+    pub fn peek_matches<F: Fn(char) -> bool>(&self, predicates: &[F]) -> bool {
+        predicates.iter().enumerate()
+            .all(|(offset, predicate)| self.peek(offset).is_some_and(predicate))
+    }
+
+    /// Creates and stores a token with the given category containing any
+    /// data processed using `advance` since the last call to this method.
+    /// Does nothing if the current position precedes the token start.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use luthor::token::Category;
+    /// let mut lexer = luthor::tokenizer::new("luthor");
+    /// lexer.advance();
+    /// lexer.advance();
+    /// lexer.tokenize(Category::Text);
+    /// assert_eq!(lexer.tokens()[0].lexeme, "lu");
+    /// ```
+    pub fn tokenize(&mut self, category: C) {
+        if let Some(token) = self.preview_token(category) {
+            self.tokens.push(token);
+            self.ignore();
+        }
+    }
+
+    /// Alias for `tokenize`, matching the vocabulary of other lexers.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use luthor::token::Category;
+    /// let mut lexer = luthor::tokenizer::new("luthor");
+    /// lexer.advance();
+    /// lexer.advance();
+    /// lexer.emit(Category::Text);
+    /// assert_eq!(lexer.tokens()[0].lexeme, "lu");
+    /// ```
+    pub fn emit(&mut self, category: C) {
+        self.tokenize(category);
+    }
+
+    /// Returns the token that `tokenize` would create with the given
+    /// category, without storing it or otherwise changing any state.
+    /// Returns None if the current position precedes the token start.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use luthor::token::Category;
+    /// let mut lexer = luthor::tokenizer::new("luthor");
+    /// assert!(lexer.preview_token(Category::Text).is_none());
+    /// lexer.advance();
+    /// lexer.advance();
+    /// assert_eq!(lexer.preview_token(Category::Text).unwrap().lexeme, "lu");
+    /// assert!(lexer.tokens().is_empty());
+    /// ```
+    pub fn preview_token(&self, category: C) -> Option<Token<C>> {
+        if self.token_start >= self.token_position {
+            return None;
+        }
+
+        Some(Token{
+            lexeme: self.current_lexeme().to_string(),
+            category,
+            start: self.token_start,
+            end: self.token_position,
+            span: Span{
+                start_line: self.start_line,
+                start_col: self.start_column,
+                end_line: self.line,
+                end_col: self.column,
+            },
+        })
+    }
+
+    /// Creates and stores a token with the given category, as `tokenize`
+    /// would, if the data processed since the last token matches the
+    /// keyword, ignoring ASCII case. Returns whether or not it matched.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use luthor::token::Category;
+    ///
+    /// let mut lexer = luthor::tokenizer::new("SELECT");
+    /// lexer.advance_by(6);
+    /// assert!(!lexer.tokenize_keyword_ci("from", Category::Keyword));
+    /// assert!(lexer.tokenize_keyword_ci("select", Category::Keyword));
+    /// assert_eq!(lexer.tokens()[0].lexeme, "SELECT");
+    /// ```
+    pub fn tokenize_keyword_ci(&mut self, keyword: &str, category: C) -> bool {
+        let matched = !keyword.is_empty() && self.current_lexeme().eq_ignore_ascii_case(keyword);
+        if matched {
+            self.tokenize(category);
+        }
+
+        matched
+    }
+
+    /// Records an error with the given message at the current position,
+    /// for lexers that encounter malformed data. Lexing can continue
+    /// afterwards, so the data is still tokenized.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let mut lexer = luthor::tokenizer::new("\"luthor");
+    /// lexer.advance_by(7);
+    /// lexer.tokenize_error("unterminated string");
+    /// assert_eq!(lexer.errors()[0].message, "unterminated string");
+    /// assert_eq!(lexer.errors()[0].position, 7);
+    /// ```
+    pub fn tokenize_error(&mut self, message: &str) {
+        self.errors.push(LexError{
+            message: message.to_string(),
+            position: self.token_position,
+        });
+    }
+
+    /// Returns the errors recorded to date.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let lexer = luthor::tokenizer::new("luthor");
+    /// assert!(lexer.errors().is_empty());
+    /// ```
+    pub fn errors(&self) -> &[LexError] {
+        &self.errors
+    }
+
+    /// Discards any data processed using `advance` since the
+    /// last token was created, without creating a token for it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use luthor::token::Category;
+    /// let mut lexer = luthor::tokenizer::new("luthor");
+    /// lexer.advance();
+    /// lexer.advance();
+    /// lexer.ignore();
+    /// lexer.advance();
+    /// lexer.tokenize(Category::Text);
+    /// assert_eq!(lexer.tokens()[0].lexeme, "t");
+    /// ```
+    pub fn ignore(&mut self) {
+        self.token_start = self.token_position;
+        self.start_line = self.line;
+        self.start_column = self.column;
+    }
+
+    /// Runs a lexer to completion, starting with the given state function
+    /// and following the state functions it returns until there are none.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use luthor::token::Category;
+    /// use luthor::tokenizer::{StateFunction, Tokenizer};
+    ///
+    /// fn initial_state(lexer: &mut Tokenizer) -> Option<StateFunction> {
+    ///     lexer.tokenize_next(1, Category::Text);
+    ///     if lexer.has_more_data() {
+    ///         Some(StateFunction::new(initial_state))
+    ///     } else {
+    ///         None
+    ///     }
+    /// }
+    ///
+    /// let mut lexer = luthor::tokenizer::new("luthor");
+    /// lexer.run(StateFunction::new(initial_state));
+    /// assert_eq!(lexer.tokens().len(), 6);
+    /// ```
+    pub fn run(&mut self, start: StateFunction<C>) {
+        let mut state_function = start;
+        loop {
+            let StateFunction(mut actual_function) = state_function;
+            match actual_function(self) {
+                Some(f) => state_function = f,
+                None => return,
+            }
+        }
+    }
+
+    /// Captures the current cursor and token count,
+    /// for speculative lexing that may need to be undone.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let mut lexer = luthor::tokenizer::new("luthor");
+    /// lexer.advance();
+    /// let checkpoint = lexer.checkpoint();
+    /// assert_eq!(checkpoint.token_position, 1);
+    /// ```
+    pub fn checkpoint(&self) -> Checkpoint {
+        Checkpoint{
+            token_start: self.token_start,
+            token_position: self.token_position,
+            token_count: self.tokens.len(),
+            error_count: self.errors.len(),
+            line: self.line,
+            column: self.column,
+            start_line: self.start_line,
+            start_column: self.start_column,
+        }
+    }
+
+    /// Restores the cursor captured by the given checkpoint, discarding
+    /// any tokens created and errors recorded after it was captured.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use luthor::token::Category;
+    ///
+    /// let mut lexer = luthor::tokenizer::new("luthor");
+    /// let checkpoint = lexer.checkpoint();
+    /// lexer.tokenize_next(3, Category::Keyword);
+    /// lexer.restore(checkpoint);
+    /// assert!(lexer.tokens().is_empty());
+    /// assert_eq!(lexer.current_char().unwrap(), 'l');
+    /// ```
+    pub fn restore(&mut self, checkpoint: Checkpoint) {
+        self.token_start = checkpoint.token_start;
+        self.token_position = checkpoint.token_position;
+        self.line = checkpoint.line;
+        self.column = checkpoint.column;
+        self.start_line = checkpoint.start_line;
+        self.start_column = checkpoint.start_column;
+        self.tokens.truncate(checkpoint.token_count);
+        self.errors.truncate(checkpoint.error_count);
+    }
+
+    /// Returns the data between the cursors captured by the given
+    /// checkpoints, which is empty if the end precedes the start.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let mut lexer = luthor::tokenizer::new("luthor");
+    /// let start = lexer.checkpoint();
+    /// lexer.advance_by(3);
+    /// let end = lexer.checkpoint();
+    /// assert_eq!(lexer.slice_between(start, end), "lut");
+    /// ```
+    pub fn slice_between(&self, start: Checkpoint, end: Checkpoint) -> &str {
+        self.slice(start.token_position, end.token_position)
+    }
+
+    // Moves the cursor to the given character position, recomputing
+    // its line and column by counting back to the preceding newline.
+    fn move_to(&mut self, position: usize) {
+        let preceding_data = self.slice(0, position);
+        let newline_count = preceding_data.matches('\n').count();
+        let column = self.unit_count(preceding_data.rsplit('\n').next().unwrap()) + 1;
+
+        self.token_position = position;
+        self.line = newline_count + 1;
+        self.column = column;
+    }
+
+    // Caches the byte offset of each character in the data, or of each
+    // grapheme cluster if the tokenizer processes data in those units.
+    fn cache_offsets(&mut self) {
+        self.char_offsets.clear();
+
+        #[cfg(feature = "unicode-segmentation")]
+        {
+            if self.graphemes {
+                self.char_offsets.extend(self.data.grapheme_indices(true).map(|(offset, _)| offset));
+                self.char_count = self.char_offsets.len();
+                return;
+            }
+        }
+
+        self.char_offsets.extend(self.data.char_indices().map(|(offset, _)| offset));
+        self.char_count = self.char_offsets.len();
+    }
+
+    // Counts the characters or grapheme clusters in the given data,
+    // depending on the units in which the tokenizer processes data.
+    fn unit_count(&self, data: &str) -> usize {
+        #[cfg(feature = "unicode-segmentation")]
+        {
+            if self.graphemes {
+                return data.graphemes(true).count();
+            }
+        }
+
+        data.chars().count()
+    }
+
+    // Returns the character or grapheme cluster at the current position.
+    fn current_unit(&self) -> Option<&str> {
+        if self.token_position < self.char_count {
+            Some(self.slice(self.token_position, self.token_position + 1))
+        } else {
+            None
+        }
+    }
+
+    // Returns the data between the given character positions, which is
+    // empty if the end precedes the start. The data is public, so if it's
+    // been replaced without a reset, the cached offsets may not fall on its
+    // character boundaries; the slice is empty then, rather than panicking.
+    fn slice(&self, start: usize, end: usize) -> &str {
+        if start < end {
+            self.data.get(self.offset_of(start)..self.offset_of(end)).unwrap_or("")
+        } else {
+            ""
+        }
+    }
+
+    // Maps a character position to its byte offset in the data, using
+    // the offsets cached at construction. Positions at or beyond the end
+    // of the data map to its length, which keeps range slicing valid.
+    fn offset_of(&self, position: usize) -> usize {
+        if position < self.char_count {
+            self.char_offsets[position]
+        } else {
+            self.data.len()
+        }
+    }
+}
+
+// Conveniences that create tokens with the built-in generic
+// categories are only available with the default category type.
+impl Tokenizer {
+    /// Creates and stores a token with the given category and the
+    /// next `amount` characters of the data. Before doing this, it
+    /// tokenizes any previously processed characters with the generic
+    /// Category::Text category. A zero amount only does the latter.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use luthor::token::Category;
+    ///
+    /// let mut lexer = luthor::tokenizer::new("luthor");
+    /// lexer.advance();
+    /// lexer.tokenize_next(5, Category::Keyword);
+    /// assert_eq!(lexer.tokens()[0].lexeme, "l");
+    /// assert_eq!(lexer.tokens()[0].category, Category::Text);
+    /// assert_eq!(lexer.tokens()[1].lexeme, "uthor");
+    /// assert_eq!(lexer.tokens()[1].category, Category::Keyword);
+    /// ```
+    pub fn tokenize_next(&mut self, amount: usize, category: Category) {
+        self.tokenize(Category::Text);
+        self.advance_by(amount);
+        self.tokenize(category);
+    }
+
+    /// Creates and stores a token with the given category and the rest of
+    /// the data, as `tokenize_next` would. Before doing this, it tokenizes
+    /// any previously processed characters with the generic Category::Text
+    /// category.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use luthor::token::Category;
+    ///
+    /// let mut lexer = luthor::tokenizer::new("# luthor");
+    /// lexer.tokenize_remaining(Category::Comment);
+    /// assert_eq!(lexer.tokens()[0].lexeme, "# luthor");
+    /// assert!(!lexer.has_more_data());
+    /// ```
+    pub fn tokenize_remaining(&mut self, category: Category) {
+        let amount = self.remaining_len();
+        self.tokenize_next(amount, category);
+    }
+
+    /// Creates and stores a token with the given category and the
+    /// character at the current position, as `tokenize_next` would.
+    /// Before doing this, it tokenizes any previously processed
+    /// characters with the generic Category::Text category.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use luthor::token::Category;
+    ///
+    /// let mut lexer = luthor::tokenizer::new("(luthor)");
+    /// lexer.tokenize_char(Category::Parenthesis);
+    /// assert_eq!(lexer.tokens()[0].lexeme, "(");
+    /// assert_eq!(lexer.current_char().unwrap(), 'l');
+    /// ```
+    pub fn tokenize_char(&mut self, category: Category) {
+        self.tokenize_next(1, category);
+    }
+
+    /// Creates and stores a whitespace token containing any consecutive
+    /// whitespace at the current position. Before doing this, it tokenizes
+    /// any previously processed characters with the generic Category::Text
+    /// category. Does nothing if the current character isn't whitespace.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use luthor::token::Category;
+    ///
+    /// let mut lexer = luthor::tokenizer::new(" \tluthor");
+    /// lexer.skip_whitespace();
+    /// assert_eq!(lexer.tokens()[0].lexeme, " \t");
+    /// assert_eq!(lexer.tokens()[0].category, Category::Whitespace);
+    /// assert_eq!(lexer.current_char().unwrap(), 'l');
+    /// ```
+    pub fn skip_whitespace(&mut self) {
+        if self.current_char().is_some_and(char::is_whitespace) {
+            self.tokenize(Category::Text);
+            self.consume_while(char::is_whitespace);
+            self.tokenize(Category::Whitespace);
+        }
+    }
+
+    /// Cleans up the tokens processed to date by discarding those
+    /// with empty lexemes and merging consecutive Category::Text
+    /// tokens. Tokens with other categories are left as-is.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use luthor::token::Category;
+    ///
+    /// let mut lexer = luthor::tokenizer::new("luthor");
+    /// lexer.tokenize_next(2, Category::Text);
+    /// lexer.tokenize_next(4, Category::Text);
+    /// lexer.normalize();
+    /// assert_eq!(lexer.tokens()[0].lexeme, "luthor");
+    /// ```
+    pub fn normalize(&mut self) {
+        let tokens = mem::take(&mut self.tokens);
+
+        for token in tokens.into_iter().filter(|token| !token.is_empty()) {
+            match self.tokens.last_mut() {
+                Some(previous) if previous.category == Category::Text &&
+                    token.category == Category::Text => previous.merge(&token),
+                _ => self.tokens.push(token),
+            }
+        }
+    }
+
+    /// Creates and stores a token with the given category and the
+    /// longest of the candidates found at the current position, as
+    /// `tokenize_next` would. Returns false without advancing if
+    /// none of the candidates are found.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use luthor::token::Category;
+    ///
+    /// let mut lexer = luthor::tokenizer::new("<=>");
+    /// assert!(lexer.tokenize_match(&["<", "<=", "=>"], Category::Operator));
+    /// assert_eq!(lexer.tokens()[0].lexeme, "<=");
+    /// assert!(!lexer.tokenize_match(&["<"], Category::Operator));
+    /// assert_eq!(lexer.current_char().unwrap(), '>');
+    /// ```
+    pub fn tokenize_match(&mut self, candidates: &[&str], category: Category) -> bool {
+        let longest_match = candidates.iter()
+            .filter(|candidate| !candidate.is_empty() && self.starts_with(candidate))
+            .max_by_key(|candidate| candidate.len());
+
+        match longest_match {
+            Some(candidate) => {
+                let amount = self.unit_count(candidate);
+                self.tokenize_next(amount, category);
+                true
+            },
+            None => false,
+        }
+    }
+
+    /// Creates and stores a token with the given category and the string
+    /// delimited by the quote at the current position, as `tokenize_next`
+    /// would. Backslashes escape the character that follows them, so
+    /// escaped quotes don't end the string. Returns false if the string
+    /// is unterminated, in which case the rest of the data is tokenized,
+    /// or without advancing if the current character isn't the quote.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use luthor::token::Category;
+    ///
+    /// let mut lexer = luthor::tokenizer::new("'it\\'s' luthor");
+    /// assert!(lexer.tokenize_string('\'', Category::String));
+    /// assert_eq!(lexer.tokens()[0].lexeme, "'it\\'s'");
+    /// ```
+    pub fn tokenize_string(&mut self, quote: char, category: Category) -> bool {
+        if self.current_char() != Some(quote) {
+            return false;
+        }
+
+        self.tokenize(Category::Text);
+        self.advance();
+        let mut terminated = false;
+        while let Some(c) = self.current_char() {
+            self.advance();
+            if c == quote {
+                terminated = true;
+                break;
+            } else if c == '\\' {
+                self.advance();
+            }
+        }
+        self.tokenize(category);
+
+        terminated
+    }
+
+    /// Creates and stores a token with the given category and the line
+    /// comment starting with the prefix at the current position, up to but
+    /// not including the next newline, as `tokenize_next` would. Returns
+    /// false without advancing if the prefix isn't found.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use luthor::token::Category;
+    ///
+    /// let mut lexer = luthor::tokenizer::new("# luthor\n");
+    /// assert!(lexer.tokenize_line_comment("#", Category::Comment));
+    /// assert_eq!(lexer.tokens()[0].lexeme, "# luthor");
+    /// assert_eq!(lexer.current_char().unwrap(), '\n');
+    /// ```
+    pub fn tokenize_line_comment(&mut self, prefix: &str, category: Category) -> bool {
+        if prefix.is_empty() || !self.starts_with(prefix) {
+            return false;
+        }
+
+        self.tokenize(Category::Text);
+        self.consume_until(|c| c == '\n');
+        self.tokenize(category);
+
+        true
+    }
+
+    /// Creates and stores a token with the given category and the block
+    /// comment delimited by the opening delimiter at the current position, as
+    /// `tokenize_next` would. If the comment is nested, each inner opening
+    /// delimiter must be closed before the comment ends. Returns false if the
+    /// comment is unterminated, in which case the rest of the data is
+    /// tokenized, or without advancing if the opening delimiter isn't found.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use luthor::token::Category;
+    ///
+    /// let mut lexer = luthor::tokenizer::new("/* /* luthor */ */");
+    /// assert!(lexer.tokenize_block_comment("/*", "*/", true, Category::Comment));
+    /// assert_eq!(lexer.tokens()[0].lexeme, "/* /* luthor */ */");
+    /// ```
+    pub fn tokenize_block_comment(&mut self, open: &str, close: &str, nested: bool, category: Category) -> bool {
+        if open.is_empty() || close.is_empty() || !self.starts_with(open) {
+            return false;
+        }
+
+        self.tokenize(Category::Text);
+        self.advance_by(self.unit_count(open));
+        let mut depth = 1;
+        while self.has_more_data() {
+            if self.starts_with(close) {
+                self.advance_by(self.unit_count(close));
+                depth -= 1;
+                if depth == 0 {
+                    break;
+                }
+            } else if nested && self.starts_with(open) {
+                self.advance_by(self.unit_count(open));
+                depth += 1;
+            } else {
+                self.advance();
+            }
+        }
+        self.tokenize(category);
+
+        depth == 0
+    }
+
+    /// Creates and stores a token with the given category and the number
+    /// at the current position, including an optional sign, fraction and
+    /// exponent, as `tokenize_next` would. A fraction or exponent is only
+    /// part of the number if it contains at least one digit. Returns false
+    /// without advancing if there isn't a number at the current position.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use luthor::token::Category;
+    ///
+    /// let mut lexer = luthor::tokenizer::new("-1.5e3;");
+    /// assert!(lexer.tokenize_number(Category::Float));
+    /// assert_eq!(lexer.tokens()[0].lexeme, "-1.5e3");
+    /// assert_eq!(lexer.current_char().unwrap(), ';');
+    /// ```
+    pub fn tokenize_number(&mut self, category: Category) -> bool {
+        let is_digit = |c: char| c.is_ascii_digit();
+        let sign = match self.current_char() {
+            Some('+') | Some('-') => 1,
+            _ => 0,
+        };
+        if !self.peek(sign).is_some_and(is_digit) {
+            return false;
+        }
+
+        self.tokenize(Category::Text);
+        self.advance_by(sign);
+        self.consume_while(is_digit);
+        if self.current_char() == Some('.') && self.peek_char().is_some_and(is_digit) {
+            self.advance();
+            self.consume_while(is_digit);
+        }
+        if self.current_char().is_some_and(|c| c == 'e' || c == 'E') {
+            let exponent_start = match self.peek_char() {
+                Some('+') | Some('-') => 2,
+                _ => 1,
+            };
+            if self.peek(exponent_start).is_some_and(is_digit) {
+                self.advance_by(exponent_start);
+                self.consume_while(is_digit);
+            }
+        }
+        self.tokenize(category);
+
+        true
+    }
+
+    /// Creates and stores a token with the given category and the identifier
+    /// at the current position, as `tokenize_next` would. The identifier's
+    /// first character must satisfy `is_start`, and the rest of it is made
+    /// up of the characters that follow which satisfy `is_continue`. Returns
+    /// false without advancing if there isn't an identifier.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use luthor::token::Category;
+    ///
+    /// let mut lexer = luthor::tokenizer::new("$luthor()");
+    /// let is_start = |c: char| c.is_alphabetic() || c == '$';
+    /// assert!(lexer.tokenize_identifier(is_start, char::is_alphanumeric, Category::Identifier));
+    /// assert_eq!(lexer.tokens()[0].lexeme, "$luthor");
+    /// ```
+    pub fn tokenize_identifier<S, F>(&mut self, is_start: S, is_continue: F, category: Category) -> bool
+        where S: Fn(char) -> bool, F: Fn(char) -> bool {
+        if !self.current_char().is_some_and(is_start) {
+            return false;
+        }
+
+        self.tokenize(Category::Text);
+        self.advance();
+        self.consume_while(is_continue);
+        self.tokenize(category);
+
+        true
+    }
+}
+
+/// The TokenStream type lazily runs a lexer, advancing through its
+/// state functions only as far as is needed to produce each token.
+/// Tokens are left in place in the tokenizer and yielded as copies,
+/// so states can still look back at them (e.g. with last_token) and
+/// restore checkpoints.
+pub struct TokenStream<C = Category> {
+    tokenizer: Tokenizer<C>,
+    state_function: Option<StateFunction<C>>,
+    index: usize,
+}
+
+impl<C> TokenStream<C> {
+    /// Initializes a new token stream that runs the
+    /// given tokenizer, starting with the given state.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use luthor::token::Category;
+    /// use luthor::tokenizer::{StateFunction, TokenStream, Tokenizer};
+    ///
+    /// fn initial_state(lexer: &mut Tokenizer) -> Option<StateFunction> {
+    ///     lexer.tokenize_next(1, Category::Text);
+    ///     lexer.current_char().map(|_| StateFunction::new(initial_state))
+    /// }
+    ///
+    /// let lexer = luthor::tokenizer::new("luthor");
+    /// let mut stream = TokenStream::new(lexer, StateFunction::new(initial_state));
+    /// assert_eq!(stream.next().unwrap().lexeme, "l");
+    /// ```
+    pub fn new(tokenizer: Tokenizer<C>, start: StateFunction<C>) -> TokenStream<C> {
+        TokenStream{
+            tokenizer,
+            state_function: Some(start),
+            index: 0,
+        }
+    }
+}
+
+impl<C: Clone> Iterator for TokenStream<C> {
+    type Item = Token<C>;
+
+    fn next(&mut self) -> Option<Token<C>> {
+        while self.index >= self.tokenizer.tokens.len() {
+            let StateFunction(mut actual_function) = self.state_function.take()?;
+            self.state_function = actual_function(&mut self.tokenizer);
+
+            // A state may have restored a checkpoint, discarding
+            // tokens that were already yielded.
+            self.index = min(self.index, self.tokenizer.tokens.len());
+        }
+
+        self.index += 1;
+        Some(self.tokenizer.tokens[self.index - 1].clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::new;
+    use super::from_reader;
+    use super::with_capacity;
+    use super::with_tokens;
+    use super::StateFunction;
+    use super::TokenStream;
+    use super::Tokenizer;
+    use std::rc::Rc;
+    use std::io::Cursor;
+    use super::super::token::Token;
+    use super::super::token::Category;
+    use super::super::span::Span;
+    use super::super::span::Position;
+    use super::super::error::LexError;
+
+    #[test]
+    fn new_initializes_correctly_with_unicode_data() {
+        let lexer_data = "différent";
+        let lexer = new(lexer_data);
+        assert_eq!(lexer.data, lexer_data);
+        assert_eq!(lexer.char_count, 9);
+        assert_eq!(lexer.char_offsets, vec![0, 1, 2, 3, 4, 6, 7, 8, 9]);
+        assert_eq!(lexer.token_start, 0);
+        assert_eq!(lexer.token_position, 0);
+        assert_eq!(lexer.line, 1);
+        assert_eq!(lexer.column, 1);
+        assert_eq!(lexer.tokens, vec![]);
+    }
+
+    #[test]
+    fn with_capacity_reserves_space_for_tokens() {
+        let lexer = with_capacity("élégant", 100);
+        assert!(lexer.tokens.capacity() >= 100);
+        assert_eq!(lexer.char_count, 7);
+    }
+
+    #[test]
+    fn with_tokens_precedes_new_tokens_with_the_given_ones() {
+        let mut heredoc = new("élé");
+        heredoc.tokenize_next(3, Category::String);
+        let heredoc_tokens = heredoc.into_tokens();
+
+        let mut lexer = with_tokens("gant", heredoc_tokens.clone());
+        assert_eq!(lexer.token_position, 0);
+        lexer.tokenize_next(4, Category::Identifier);
+
+        let tokens = lexer.tokens();
+        assert_eq!(tokens.len(), 2);
+        assert_eq!(tokens[0], heredoc_tokens[0]);
+        assert_eq!(tokens[1].lexeme, "gant");
+        assert_eq!(tokens[1].category, Category::Identifier);
+    }
+
+    #[test]
+    fn from_reader_lexes_identically_to_new() {
+        let lexer_data = "123élé45gant";
+        let mut lexer = new(lexer_data);
+        lexer.run(StateFunction::new(digits));
+
+        let reader = Cursor::new(lexer_data.as_bytes().to_vec());
+        let mut reader_lexer = from_reader(reader).unwrap();
+        reader_lexer.run(StateFunction::new(digits));
+
+        assert_eq!(reader_lexer.data, lexer.data);
+        assert_eq!(reader_lexer.char_offsets, lexer.char_offsets);
+        assert_eq!(reader_lexer.tokens, lexer.tokens);
+    }
+
+    #[test]
+    fn from_reader_fails_with_invalid_utf8_data() {
+        let reader = Cursor::new(vec![0x6c, 0xff, 0x75]);
+        assert!(from_reader(reader).is_err());
+    }
+
+    #[derive(PartialEq, Debug, Clone)]
+    enum Markup {
+        Tag,
+        Content,
+    }
+
+    #[test]
+    fn new_initializes_a_tokenizer_with_a_custom_category_type() {
+        let mut lexer: Tokenizer<Markup> = Tokenizer::new("<b>élégant</b>");
+        lexer.advance_by(3);
+        lexer.tokenize(Markup::Tag);
+        lexer.consume_until(|c| c == '<');
+        lexer.tokenize(Markup::Content);
+        lexer.advance_by(4);
+        lexer.tokenize(Markup::Tag);
+
+        let tokens: Vec<(String, Markup)> = lexer.into_tokens().into_iter()
+            .map(|token| (token.lexeme, token.category)).collect();
+        assert_eq!(tokens, vec![
+            ("<b>".to_string(), Markup::Tag),
+            ("élégant".to_string(), Markup::Content),
+            ("</b>".to_string(), Markup::Tag),
+        ]);
+    }
+
+    #[test]
+    fn tokens_ref_matches_the_processed_tokens() {
+        let mut lexer = new("élégant");
+        lexer.advance_by(2);
+        lexer.tokenize_next(5, Category::Keyword);
+
+        assert_eq!(lexer.tokens_ref().len(), 2);
+        assert_eq!(lexer.tokens_ref(), &lexer.tokens()[..]);
+    }
+
+    #[test]
+    fn token_count_increases_as_tokens_are_created() {
+        let mut lexer = new("fn élégant");
+        assert_eq!(lexer.token_count(), 0);
+        assert!(lexer.is_empty());
+
+        lexer.tokenize_next(2, Category::Keyword);
+        assert_eq!(lexer.token_count(), 1);
+        assert!(!lexer.is_empty());
+
+        lexer.skip_whitespace();
+        lexer.tokenize_next(7, Category::Identifier);
+        assert_eq!(lexer.token_count(), 3);
+    }
+
+    #[test]
+    fn last_token_returns_the_most_recent_token() {
+        let mut lexer = new("fn élégant");
+        assert_eq!(lexer.last_token(), None);
+
+        lexer.tokenize_next(2, Category::Keyword);
+        lexer.advance();
+        lexer.tokenize_next(7, Category::Identifier);
+
+        let expected_token = Token{
+            lexeme: "élégant".to_string(),
+            category: Category::Identifier,
+            start: 3,
+            end: 10,
+            span: Span{ start_line: 1, start_col: 4, end_line: 1, end_col: 11 },
+        };
+        assert_eq!(lexer.last_token(), Some(&expected_token));
+    }
+
+    #[test]
+    fn set_category_replaces_the_category_of_a_token() {
+        let mut lexer = new("fn élégant");
+        lexer.tokenize_next(2, Category::Text);
+        lexer.skip_whitespace();
+        lexer.tokenize_next(7, Category::Text);
+
+        assert!(lexer.set_category(0, Category::Keyword));
+        assert!(lexer.set_category(2, Category::Identifier));
+        assert!(!lexer.set_category(3, Category::Identifier));
+
+        let categories: Vec<Category> = lexer.tokens().into_iter()
+            .map(|token| token.category).collect();
+        assert_eq!(categories, vec![Category::Keyword, Category::Whitespace, Category::Identifier]);
+    }
+
+    #[test]
+    fn token_at_offset_finds_the_token_covering_the_offset() {
+        let mut lexer = new("fn élégant() {}");
+        lexer.tokenize_next(2, Category::Keyword);
+        lexer.skip_whitespace();
+        lexer.tokenize_next(7, Category::Identifier);
+        lexer.advance_by(3);
+        lexer.ignore();
+        lexer.tokenize_next(3, Category::Brace);
+
+        assert_eq!(lexer.token_at_offset(0).unwrap().lexeme, "fn");
+        assert_eq!(lexer.token_at_offset(2).unwrap().lexeme, " ");
+        assert_eq!(lexer.token_at_offset(6).unwrap().lexeme, "élégant");
+        assert_eq!(lexer.token_at_offset(9).unwrap().lexeme, "élégant");
+        assert_eq!(lexer.token_at_offset(10), None);
+        assert_eq!(lexer.token_at_offset(14).unwrap().lexeme, "{}");
+        assert_eq!(lexer.token_at_offset(15), None);
+    }
+
+    #[test]
+    fn tokens_of_returns_tokens_with_the_category_in_order() {
+        let mut lexer = new("if élégant then x");
+        lexer.tokenize_next(2, Category::Keyword);
+        lexer.skip_whitespace();
+        lexer.tokenize_next(7, Category::Identifier);
+        lexer.skip_whitespace();
+        lexer.tokenize_next(4, Category::Keyword);
+        lexer.skip_whitespace();
+        lexer.tokenize_next(1, Category::Identifier);
+
+        let keywords: Vec<String> = lexer.tokens_of(Category::Keyword).into_iter()
+            .map(|token| token.lexeme).collect();
+        assert_eq!(keywords, vec!["if", "then"]);
+        assert!(lexer.tokens_of(Category::Comment).is_empty());
+        assert_eq!(lexer.tokens.len(), 7);
+    }
+
+    #[test]
+    fn contains_category_checks_for_a_token_with_the_category() {
+        let mut lexer = new("if élégant");
+        assert!(!lexer.contains_category(Category::Keyword));
+
+        lexer.tokenize_next(2, Category::Keyword);
+        lexer.skip_whitespace();
+        lexer.tokenize_next(7, Category::Identifier);
+        assert!(lexer.contains_category(Category::Keyword));
+        assert!(lexer.contains_category(Category::Identifier));
+        assert!(!lexer.contains_category(Category::Comment));
+    }
+
+    #[test]
+    fn category_counts_counts_tokens_by_category() {
+        let mut lexer = new("if élégant then x // y");
+        lexer.tokenize_next(2, Category::Keyword);
+        lexer.skip_whitespace();
+        lexer.tokenize_next(7, Category::Identifier);
+        lexer.skip_whitespace();
+        lexer.tokenize_next(4, Category::Keyword);
+        lexer.skip_whitespace();
+        lexer.tokenize_next(1, Category::Identifier);
+        lexer.skip_whitespace();
+        lexer.tokenize_next(4, Category::Comment);
+        let counts = lexer.category_counts();
+
+        assert_eq!(counts.len(), 4);
+        assert_eq!(counts[&Category::Keyword], 2);
+        assert_eq!(counts[&Category::Identifier], 2);
+        assert_eq!(counts[&Category::Whitespace], 4);
+        assert_eq!(counts[&Category::Comment], 1);
+        assert_eq!(counts.get(&Category::String), None);
+    }
+
+    #[test]
+    fn into_tokens_returns_the_processed_tokens() {
+        let mut lexer = new("élégant");
+        lexer.advance_by(2);
+        lexer.tokenize_next(5, Category::Keyword);
+        let tokens = lexer.tokens();
+
+        assert_eq!(lexer.into_tokens(), tokens);
+    }
+
+    #[test]
+    fn reset_reinitializes_the_tokenizer_with_new_data() {
+        let mut lexer = new("luthor\nlexer");
+        lexer.advance_by(8);
+        lexer.tokenize(Category::Text);
+        lexer.advance();
+        lexer.reset("différent");
+
+        let fresh_lexer = new("différent");
+        assert_eq!(lexer.data, fresh_lexer.data);
+        assert_eq!(lexer.char_count, fresh_lexer.char_count);
+        assert_eq!(lexer.char_offsets, fresh_lexer.char_offsets);
+        assert_eq!(lexer.token_start, 0);
+        assert_eq!(lexer.token_position, 0);
+        assert_eq!(lexer.position(), (1, 1));
+        assert_eq!(lexer.tokens, vec![]);
+
+        lexer.advance_by(2);
+        lexer.tokenize(Category::Text);
+        assert_eq!(lexer.tokens[0].span, Span{ start_line: 1, start_col: 1, end_line: 1, end_col: 3 });
+    }
+
+    #[test]
+    fn reset_retains_token_capacity() {
+        let mut lexer = new("luthor");
+        lexer.tokenize_next(6, Category::Text);
+        let capacity = lexer.tokens.capacity();
+        lexer.reset("lexer");
+
+        assert_eq!(lexer.tokens.capacity(), capacity);
+    }
+
+    #[test]
+    fn advance_increments_the_cursor_by_one() {
+        let lexer_data = "élégant";
+        let mut lexer = new(lexer_data);
+        lexer.advance();
+        assert_eq!(lexer.token_position, 1);
+        lexer.advance();
+        assert_eq!(lexer.token_position, 2);
+    }
+
+    #[test]
+    fn advance_stops_when_there_is_no_more_data() {
+        let lexer_data = "élégant";
+        let mut lexer = new(lexer_data);
+
+        // Try to go beyond the last character.
+        for _ in 0..lexer.char_count {
+            lexer.advance();
+        }
+
+        assert_eq!(lexer.token_position, lexer.char_count);
+    }
+
+    #[test]
+    fn advance_by_increments_the_cursor_by_the_amount() {
+        let lexer_data = "élégant";
+        let mut lexer = new(lexer_data);
+        lexer.advance_by(3);
+        assert_eq!(lexer.token_position, 3);
+        lexer.advance_by(2);
+        assert_eq!(lexer.token_position, 5);
+    }
+
+    #[test]
+    fn advance_by_stops_when_there_is_no_more_data() {
+        let lexer_data = "élégant";
+        let mut lexer = new(lexer_data);
+        lexer.advance_by(15);
+
+        assert_eq!(lexer.token_position, lexer.char_count);
+        assert!(!lexer.has_more_data());
+    }
+
+    #[test]
+    fn seek_moves_the_cursor_forward() {
+        let mut lexer = new("élé\ngant");
+        lexer.advance();
+        lexer.seek(6);
+
+        assert_eq!(lexer.token_start, 6);
+        assert_eq!(lexer.token_position, 6);
+        assert_eq!(lexer.position(), (2, 3));
+        assert_eq!(lexer.current_char(), Some('n'));
+    }
+
+    #[test]
+    fn seek_clamps_to_the_end_of_the_data() {
+        let mut lexer = new("élégant");
+        lexer.seek(15);
+
+        assert_eq!(lexer.token_start, 7);
+        assert_eq!(lexer.token_position, 7);
+        assert_eq!(lexer.position(), (1, 8));
+        assert!(!lexer.has_more_data());
+    }
+
+    #[test]
+    fn tokenize_starts_from_the_sought_position() {
+        let mut lexer = new("élé\ngant");
+        lexer.advance_by(5);
+        lexer.seek(2);
+        lexer.advance_by(3);
+        lexer.tokenize(Category::Text);
+
+        let expected_token = Token{
+            lexeme: "é\ng".to_string(),
+            category: Category::Text,
+            start: 2,
+            end: 5,
+            span: Span{ start_line: 1, start_col: 3, end_line: 2, end_col: 2 },
+        };
+        assert_eq!(lexer.tokens, vec![expected_token]);
+    }
+
+    #[test]
+    fn restore_discards_tokens_created_after_the_checkpoint() {
+        let mut lexer = new("fn élé\ngant");
+        lexer.tokenize_next(2, Category::Keyword);
+        lexer.advance();
+        let checkpoint = lexer.checkpoint();
+
+        lexer.tokenize_next(4, Category::Identifier);
+        lexer.skip_whitespace();
+        lexer.advance();
+        lexer.restore(checkpoint);
+
+        assert_eq!(lexer.tokens.len(), 1);
+        assert_eq!(lexer.tokens[0].lexeme, "fn");
+        assert_eq!(lexer.token_start, 2);
+        assert_eq!(lexer.token_position, 3);
+        assert_eq!(lexer.position(), (1, 4));
+
+        lexer.tokenize(Category::Whitespace);
+        let expected_token = Token{
+            lexeme: " ".to_string(),
+            category: Category::Whitespace,
+            start: 2,
+            end: 3,
+            span: Span{ start_line: 1, start_col: 3, end_line: 1, end_col: 4 },
+        };
+        assert_eq!(lexer.tokens[1], expected_token);
+    }
+
+    #[test]
+    fn slice_between_returns_the_data_between_checkpoints() {
+        let mut lexer = new("fn élé\ngant");
+        lexer.tokenize_next(3, Category::Keyword);
+        let start = lexer.checkpoint();
+        lexer.advance_by(6);
+        let end = lexer.checkpoint();
+        lexer.advance();
+
+        assert_eq!(lexer.slice_between(start, end), "élé\nga");
+        assert_eq!(lexer.slice_between(start, start), "");
+        assert_eq!(lexer.slice_between(end, start), "");
+    }
+
+    #[test]
+    fn backup_decrements_the_cursor_by_one() {
+        let lexer_data = "élégant";
+        let mut lexer = new(lexer_data);
+        lexer.advance_by(3);
+        lexer.backup();
+        assert_eq!(lexer.token_position, 2);
+        lexer.backup();
+        assert_eq!(lexer.token_position, 1);
+    }
+
+    #[test]
+    fn backup_stops_at_token_start() {
+        let lexer_data = "élégant";
+        let mut lexer = new(lexer_data);
+        lexer.advance_by(2);
+        lexer.tokenize(Category::Text);
+        lexer.advance();
+        lexer.backup();
+        lexer.backup();
+
+        assert_eq!(lexer.token_position, 2);
+        assert_eq!(lexer.token_start, 2);
+    }
+
+    #[test]
+    fn consume_while_stops_at_the_first_unmatched_char() {
+        let lexer_data = "123abc";
+        let mut lexer = new(lexer_data);
+        lexer.consume_while(|c| c.is_numeric());
+
+        assert_eq!(lexer.token_position, 3);
+        assert_eq!(lexer.current_char().unwrap(), 'a');
+    }
+
+    #[test]
+    fn consume_while_stops_when_there_is_no_more_data() {
+        let lexer_data = "élégant";
+        let mut lexer = new(lexer_data);
+        lexer.consume_while(|c| c.is_alphabetic());
+
+        assert_eq!(lexer.token_position, lexer.char_count);
+    }
+
+    #[test]
+    fn consume_until_stops_at_the_first_matched_char() {
+        let lexer_data = "\"élégant\" data";
+        let mut lexer = new(lexer_data);
+        lexer.advance();
+        lexer.consume_until(|c| c == '"');
+
+        assert_eq!(lexer.token_position, 8);
+        assert_eq!(lexer.current_char().unwrap(), '"');
+    }
+
+    #[test]
+    fn consume_until_stops_when_there_is_no_more_data() {
+        let lexer_data = "\"élégant";
+        let mut lexer = new(lexer_data);
+        lexer.advance();
+        lexer.consume_until(|c| c == '"');
+
+        assert_eq!(lexer.token_position, lexer.char_count);
+    }
+
+    #[test]
+    fn advance_to_stops_at_the_target() {
+        let lexer_data = "\"élégant\" x";
+        let mut lexer = new(lexer_data);
+        lexer.advance();
+
+        assert!(lexer.advance_to('"'));
+        assert_eq!(lexer.token_position, 8);
+        assert!(lexer.advance_to('"'));
+        assert_eq!(lexer.token_position, 8);
+    }
+
+    #[test]
+    fn advance_to_stops_when_there_is_no_more_data() {
+        let lexer_data = "\"élégant";
+        let mut lexer = new(lexer_data);
+        lexer.advance();
+
+        assert!(!lexer.advance_to('"'));
+        assert_eq!(lexer.token_position, lexer.char_count);
+    }
+
+    #[test]
+    fn advance_while_char_counts_the_run_of_the_target() {
+        let lexer_data = "###é#";
+        let mut lexer = new(lexer_data);
+
+        assert_eq!(lexer.advance_while_char('#'), 3);
+        assert_eq!(lexer.token_position, 3);
+        assert_eq!(lexer.advance_while_char('#'), 0);
+        assert_eq!(lexer.advance_while_char('é'), 1);
+        assert_eq!(lexer.advance_while_char('#'), 1);
+        assert_eq!(lexer.advance_while_char('#'), 0);
+    }
+
+    #[test]
+    fn advance_line_advances_past_the_next_newline() {
+        let lexer_data = "élé\ngant";
+        let mut lexer = new(lexer_data);
+        lexer.advance();
+
+        lexer.advance_line();
+        assert_eq!(lexer.token_position, 4);
+        assert_eq!(lexer.position(), (2, 1));
+    }
+
+    #[test]
+    fn advance_line_stops_when_there_is_no_more_data() {
+        let lexer_data = "élé\ngant";
+        let mut lexer = new(lexer_data);
+        lexer.advance_by(5);
+
+        lexer.advance_line();
+        assert_eq!(lexer.token_position, lexer.char_count);
+        assert_eq!(lexer.position(), (2, 5));
+    }
+
+    #[test]
+    fn consume_balanced_advances_past_the_matching_delimiter() {
+        let lexer_data = "(a(b)c)d";
+        let mut lexer = new(lexer_data);
+
+        assert!(lexer.consume_balanced('(', ')'));
+        assert_eq!(lexer.token_position, 7);
+        assert!(!lexer.consume_balanced('(', ')'));
+        assert_eq!(lexer.token_position, 7);
+    }
+
+    #[test]
+    fn consume_balanced_stops_when_there_is_no_more_data() {
+        let lexer_data = "(a(b)";
+        let mut lexer = new(lexer_data);
+
+        assert!(!lexer.consume_balanced('(', ')'));
+        assert_eq!(lexer.token_position, lexer.char_count);
+    }
+
+    #[test]
+    fn accept_advances_over_a_valid_char() {
+        let lexer_data = "é1";
+        let mut lexer = new(lexer_data);
+
+        assert!(lexer.accept("èé"));
+        assert_eq!(lexer.token_position, 1);
+    }
+
+    #[test]
+    fn accept_does_nothing_for_an_invalid_char() {
+        let lexer_data = "1é";
+        let mut lexer = new(lexer_data);
+
+        assert!(!lexer.accept("+-"));
+        assert_eq!(lexer.token_position, 0);
+    }
+
+    #[test]
+    fn accept_and_accept_run_scan_a_signed_number() {
+        let digits = "0123456789";
+        let mut lexer = new("+123 45");
+        lexer.accept("+-");
+        lexer.accept_run(digits);
+        lexer.tokenize(Category::Integer);
+        lexer.skip_whitespace();
+        lexer.accept("+-");
+        lexer.accept_run(digits);
+        lexer.tokenize(Category::Integer);
+
+        assert_eq!(lexer.tokens[0].lexeme, "+123");
+        assert_eq!(lexer.tokens[2].lexeme, "45");
+        assert!(!lexer.has_more_data());
+    }
+
+    #[test]
+    fn has_more_data_works() {
+        let lexer_data = "él";
+        let mut lexer = new(lexer_data);
+
+        lexer.advance();
+        assert!(lexer.has_more_data());
+
+        lexer.advance();
+        assert!(!lexer.has_more_data());
+    }
+
+    #[test]
+    fn data_len_counts_unicode_chars() {
+        let lexer = new("élégant\n✓");
+        assert_eq!(lexer.data_len(), 9);
+        assert!(lexer.data_len() < lexer.data.len());
+        assert_eq!(new("").data_len(), 0);
+    }
+
+    #[test]
+    fn remaining_len_decreases_as_the_cursor_advances() {
+        let lexer_data = "élé";
+        let mut lexer = new(lexer_data);
+
+        for expected_length in [3, 2, 1, 0, 0] {
+            assert_eq!(lexer.remaining_len(), expected_length);
+            lexer.advance();
+        }
+    }
+
+    #[test]
+    fn position_tracks_lines_and_columns_while_advancing() {
+        let lexer_data = "ab\ncd";
+        let mut lexer = new(lexer_data);
+        let expected_positions = vec![(1, 1), (1, 2), (1, 3), (2, 1), (2, 2), (2, 3)];
+
+        for expected_position in expected_positions {
+            assert_eq!(lexer.position(), expected_position);
+            lexer.advance();
+        }
+    }
+
+    #[test]
+    fn current_position_tracks_the_offset_line_and_column() {
+        let lexer_data = "élé\ngant";
+        let mut lexer = new(lexer_data);
+        assert_eq!(lexer.current_position(), Position{ offset: 0, line: 1, column: 1 });
+
+        lexer.advance_by(6);
+        assert_eq!(lexer.current_position(), Position{ offset: 6, line: 2, column: 3 });
+    }
+
+    #[test]
+    fn byte_offset_accounts_for_multibyte_chars() {
+        let lexer_data = "éa";
+        let mut lexer = new(lexer_data);
+        assert_eq!(lexer.byte_offset(), 0);
+
+        lexer.advance();
+        assert_eq!(lexer.byte_offset(), 2);
+        lexer.advance();
+        assert_eq!(lexer.byte_offset(), 3);
+        lexer.advance();
+        assert_eq!(lexer.byte_offset(), 3);
+    }
+
+    #[test]
+    fn position_counts_unicode_chars_as_single_columns() {
+        let lexer_data = "élégant";
+        let mut lexer = new(lexer_data);
+        lexer.advance_by(3);
+
+        assert_eq!(lexer.position(), (1, 4));
+    }
+
+    #[test]
+    fn backup_restores_position_across_a_newline() {
+        let lexer_data = "él\nég";
+        let mut lexer = new(lexer_data);
+        lexer.advance_by(4);
+        lexer.backup();
+        assert_eq!(lexer.position(), (2, 1));
+        lexer.backup();
+        assert_eq!(lexer.position(), (1, 3));
+        lexer.backup();
+        assert_eq!(lexer.position(), (1, 2));
+    }
+
+    #[test]
+    fn current_char_returns_the_char_at_token_position() {
+        let lexer_data = "él";
+        let lexer = new(lexer_data);
+
+        assert_eq!(lexer.current_char().unwrap(), 'é');
+    }
+
+    #[test]
+    fn current_char_returns_none_if_at_the_end() {
+        let lexer_data = "él";
+        let mut lexer = new(lexer_data);
+        lexer.advance();
+        lexer.advance();
+
+        assert_eq!(lexer.current_char(), None);
+    }
+
+    #[test]
+    fn peek_char_returns_the_char_after_token_position() {
+        let lexer_data = "él";
+        let lexer = new(lexer_data);
+
+        assert_eq!(lexer.peek_char().unwrap(), 'l');
+        assert_eq!(lexer.token_position, 0);
+    }
+
+    #[test]
+    fn peek_char_returns_none_if_at_the_last_char() {
+        let lexer_data = "él";
+        let mut lexer = new(lexer_data);
+        lexer.advance();
+
+        assert_eq!(lexer.peek_char(), None);
+    }
+
+    #[test]
+    fn peek_with_zero_offset_returns_the_current_char() {
+        let lexer_data = "élégant";
+        let lexer = new(lexer_data);
+
+        assert_eq!(lexer.peek(0), lexer.current_char());
+    }
+
+    #[test]
+    fn peek_returns_the_char_at_the_offset_from_token_position() {
+        let lexer_data = "élégant";
+        let mut lexer = new(lexer_data);
+        lexer.advance();
+
+        assert_eq!(lexer.peek(2).unwrap(), 'g');
+        assert_eq!(lexer.token_position, 1);
+    }
+
+    #[test]
+    fn peek_returns_none_if_offset_is_past_the_end() {
+        let lexer_data = "élégant";
+        let lexer = new(lexer_data);
+
+        assert_eq!(lexer.peek(7), None);
+    }
+
+    #[test]
+    fn char_at_returns_the_char_at_an_absolute_position() {
+        let lexer_data = "élégant";
+        let mut lexer = new(lexer_data);
+        lexer.advance_by(4);
+
+        assert_eq!(lexer.char_at(0), Some('é'));
+        assert_eq!(lexer.char_at(2), Some('é'));
+        assert_eq!(lexer.char_at(6), Some('t'));
+        assert_eq!(lexer.char_at(7), None);
+        assert_eq!(lexer.char_at(usize::MAX), None);
+    }
+
+    #[test]
+    fn current_lexeme_returns_the_data_since_token_start() {
+        let lexer_data = "élégant";
+        let mut lexer = new(lexer_data);
+        lexer.advance();
+        lexer.tokenize(Category::Text);
+        lexer.advance_by(3);
+
+        assert_eq!(lexer.current_lexeme(), "lég");
+    }
+
+    #[test]
+    fn current_lexeme_is_empty_if_range_is_empty() {
+        let lexer_data = "élégant";
+        let lexer = new(lexer_data);
+
+        assert_eq!(lexer.current_lexeme(), "");
+    }
+
+    #[test]
+    fn current_is_one_of_matches_whole_words() {
+        let keywords = ["fn", "élégant"];
+        let mut lexer = new("fnord élégant");
+        lexer.advance_by(2);
+        assert!(lexer.current_is_one_of(&keywords));
+
+        lexer.advance_by(3);
+        assert!(!lexer.current_is_one_of(&keywords));
+        lexer.advance();
+        lexer.ignore();
+        lexer.advance_by(7);
+        assert!(lexer.current_is_one_of(&keywords));
+        assert!(!lexer.current_is_one_of(&[]));
+    }
+
+    #[test]
+    fn pending_len_grows_until_the_next_token() {
+        let lexer_data = "élégant";
+        let mut lexer = new(lexer_data);
+        assert_eq!(lexer.pending_len(), 0);
+
+        lexer.advance();
+        assert_eq!(lexer.pending_len(), 1);
+        lexer.advance_by(2);
+        assert_eq!(lexer.pending_len(), 3);
+
+        lexer.tokenize(Category::Text);
+        assert_eq!(lexer.pending_len(), 0);
+    }
+
+    #[test]
+    fn remaining_shrinks_as_the_cursor_advances() {
+        let lexer_data = "élégant";
+        let mut lexer = new(lexer_data);
+        assert_eq!(lexer.remaining(), "élégant");
+        lexer.advance();
+        assert_eq!(lexer.remaining(), "légant");
+        lexer.advance();
+        assert_eq!(lexer.remaining(), "égant");
+    }
+
+    #[test]
+    fn remaining_is_empty_if_at_the_end() {
+        let lexer_data = "élégant";
+        let mut lexer = new(lexer_data);
+        lexer.advance_by(7);
+
+        assert_eq!(lexer.remaining(), "");
+    }
+
+    #[test]
+    fn starts_with_matches_unicode_prefixes_at_the_cursor() {
+        let lexer_data = "élégant";
+        let mut lexer = new(lexer_data);
+        lexer.advance();
+
+        assert!(lexer.starts_with("lé"));
+        assert!(!lexer.starts_with("él"));
+    }
+
+    #[test]
+    fn starts_with_is_false_if_prefix_is_longer_than_remaining_data() {
+        let lexer_data = "élégant";
+        let mut lexer = new(lexer_data);
+        lexer.advance_by(5);
+
+        assert!(!lexer.starts_with("antique"));
+    }
+
+    #[test]
+    fn peek_matches_recognizes_patterns_without_advancing() {
+        let lexer_data = "à 07:3";
+        let mut lexer = new(lexer_data);
+        let is_digit = |c: char| c.is_ascii_digit();
+        let hours: [fn(char) -> bool; 3] = [is_digit, is_digit, |c| c == ':'];
+
+        assert!(!lexer.peek_matches(&hours));
+        lexer.advance_by(2);
+        assert!(lexer.peek_matches(&hours));
+        assert_eq!(lexer.token_position, 2);
+        lexer.advance_by(3);
+        assert!(!lexer.peek_matches(&hours));
+        assert!(lexer.peek_matches::<fn(char) -> bool>(&[]));
+    }
+
+    #[test]
     fn tokenize_advances_token_start_to_cursor() {
         let lexer_data = "élégant";
         let mut lexer = new(lexer_data);
         lexer.advance();
-        lexer.advance();
-        lexer.tokenize(Category::Text);
-        
+        lexer.advance();
+        lexer.tokenize(Category::Text);
+        
+        assert_eq!(lexer.token_start, 2);
+    }
+
+    #[test]
+    fn tokenize_creates_the_correct_token() {
+        let lexer_data = "élégant";
+        let mut lexer = new(lexer_data);
+        lexer.advance();
+        lexer.advance();
+        lexer.tokenize(Category::Text);
+        
+        let token = lexer.tokens.pop().unwrap();
+        let expected_token = Token{
+            lexeme: "él".to_string(),
+            category: Category::Text,
+            start: 0,
+            end: 2,
+            span: Span{ start_line: 1, start_col: 1, end_line: 1, end_col: 3 },
+        };
+        assert_eq!(token, expected_token);
+    }
+
+    #[test]
+    fn tokenize_records_the_offsets_of_each_token() {
+        let lexer_data = "luthor";
+        let mut lexer = new(lexer_data);
+        lexer.advance_by(2);
+        lexer.tokenize(Category::Text);
+        lexer.advance_by(4);
+        lexer.tokenize(Category::Keyword);
+
+        assert_eq!(lexer.tokens[0].range(), 0..2);
+        assert_eq!(lexer.tokens[1].range(), 2..6);
+    }
+
+    #[test]
+    fn tokenize_records_the_span_of_a_token_across_lines() {
+        let lexer_data = "él\négant";
+        let mut lexer = new(lexer_data);
+        lexer.advance();
+        lexer.tokenize(Category::Text);
+        lexer.advance_by(4);
+        lexer.tokenize(Category::Text);
+
+        let span = lexer.tokens[1].span;
+        assert_eq!(span, Span{ start_line: 1, start_col: 2, end_line: 2, end_col: 3 });
+    }
+
+    #[test]
+    fn tokenize_creates_tokens_with_custom_categories() {
+        let lexer_data = "/*+ index(t) */";
+        let mut lexer = new(lexer_data);
+        lexer.tokenize_next(15, Category::Custom("sql-hint"));
+
+        let token = lexer.tokens.pop().unwrap();
+        assert_eq!(token.category, Category::Custom("sql-hint"));
+        assert!(token.category != Category::Custom("sql-comment"));
+        assert_eq!(token, token.clone());
+    }
+
+    #[test]
+    fn tokenize_does_nothing_if_range_is_empty() {
+        let lexer_data = "élégant";
+        let mut lexer = new(lexer_data);
+        lexer.tokenize(Category::Text);
+        
+        assert_eq!(lexer.tokens.len(), 0);
+        assert_eq!(lexer.token_start, 0);
+        assert_eq!(lexer.token_position, 0);
+    }
+
+    #[test]
+    fn tokenize_keyword_ci_ignores_case() {
+        let mut lexer = new("SELECT select SeLeCt selected");
+
+        for _ in 0..3 {
+            lexer.consume_until(char::is_whitespace);
+            assert!(lexer.tokenize_keyword_ci("select", Category::Keyword));
+            lexer.skip_whitespace();
+        }
+        lexer.consume_until(char::is_whitespace);
+        assert!(!lexer.tokenize_keyword_ci("select", Category::Keyword));
+
+        let keywords: Vec<String> = lexer.tokens_of(Category::Keyword).into_iter()
+            .map(|token| token.lexeme).collect();
+        assert_eq!(keywords, vec!["SELECT", "select", "SeLeCt"]);
+        assert_eq!(lexer.current_lexeme(), "selected");
+    }
+
+    #[test]
+    fn emit_creates_the_same_token_as_tokenize() {
+        let lexer_data = "élégant";
+        let mut lexer = new(lexer_data);
+        let mut other_lexer = new(lexer_data);
+        lexer.advance_by(2);
+        lexer.emit(Category::Text);
+        other_lexer.advance_by(2);
+        other_lexer.tokenize(Category::Text);
+
+        assert_eq!(lexer.tokens, other_lexer.tokens);
+    }
+
+    #[test]
+    fn preview_token_matches_the_token_created_by_tokenize() {
+        let lexer_data = "fn\nélégant";
+        let mut lexer = new(lexer_data);
+        lexer.advance_by(2);
+        lexer.tokenize(Category::Keyword);
+        lexer.advance_by(5);
+
+        let preview = lexer.preview_token(Category::Identifier);
+        assert_eq!(lexer.tokens.len(), 1);
+        assert_eq!(lexer.token_start, 2);
+        assert_eq!(lexer.token_position, 7);
+
+        lexer.tokenize(Category::Identifier);
+        assert_eq!(preview.as_ref(), lexer.last_token());
+        assert_eq!(lexer.preview_token(Category::Identifier), None);
+    }
+
+    #[test]
+    fn ignore_discards_data_without_creating_a_token() {
+        let lexer_data = "él\négant";
+        let mut lexer = new(lexer_data);
+        lexer.advance();
+        lexer.advance();
+        lexer.ignore();
+        assert_eq!(lexer.tokens.len(), 0);
+        assert_eq!(lexer.token_start, 2);
+
+        lexer.advance_by(3);
+        lexer.tokenize(Category::Text);
+        let token = lexer.tokens.pop().unwrap();
+        assert_eq!(token.lexeme, "\nég");
+        assert_eq!(token.range(), 2..5);
+        assert_eq!(token.span, Span{ start_line: 1, start_col: 3, end_line: 2, end_col: 3 });
+    }
+
+    #[test]
+    fn tokenize_does_nothing_if_position_precedes_token_start() {
+        let lexer_data = "élégant";
+        let mut lexer = new(lexer_data);
+        lexer.token_start = 4;
+        lexer.token_position = 2;
+
+        assert_eq!(lexer.current_lexeme(), "");
+        lexer.tokenize(Category::Text);
+        assert_eq!(lexer.tokens.len(), 0);
+        assert_eq!(lexer.token_start, 4);
+        assert_eq!(lexer.token_position, 2);
+    }
+
+    #[test]
+    fn tokenize_next_tokenizes_previous_data_as_text() {
+        let lexer_data = "élégant";
+        let mut lexer = new(lexer_data);
+        lexer.advance();
+        lexer.advance();
+        lexer.tokenize_next(1, Category::Keyword);
+
+        let token = lexer.tokens.remove(0);
+        let expected_token = Token{
+            lexeme: "él".to_string(),
+            category: Category::Text,
+            start: 0,
+            end: 2,
+            span: Span{ start_line: 1, start_col: 1, end_line: 1, end_col: 3 },
+        };
+        assert_eq!(token, expected_token);
+    }
+
+    #[test]
+    fn tokenize_next_tokenizes_next_x_chars() {
+        let lexer_data = "élégant";
+        let mut lexer = new(lexer_data);
+        lexer.advance();
+        lexer.advance();
+        lexer.tokenize_next(5, Category::Keyword);
+
+        let token = lexer.tokens.pop().unwrap();
+        let expected_token = Token{
+            lexeme: "égant".to_string(),
+            category: Category::Keyword,
+            start: 2,
+            end: 7,
+            span: Span{ start_line: 1, start_col: 3, end_line: 1, end_col: 8 },
+        };
+        assert_eq!(token, expected_token);
+    }
+
+    #[test]
+    fn tokenize_next_with_a_zero_amount_only_tokenizes_previous_data() {
+        let lexer_data = "élégant";
+        let mut lexer = new(lexer_data);
+        lexer.advance_by(2);
+        lexer.tokenize_next(0, Category::Keyword);
+
+        assert_eq!(lexer.tokens.len(), 1);
+        assert_eq!(lexer.tokens[0].lexeme, "él");
+        assert_eq!(lexer.tokens[0].category, Category::Text);
+        assert_eq!(lexer.token_position, 2);
+
+        lexer.tokenize_next(0, Category::Keyword);
+        assert_eq!(lexer.tokens.len(), 1);
+    }
+
+    #[test]
+    fn tokenize_next_takes_at_most_what_is_left() {
+        let lexer_data = "élégant";
+        let mut lexer = new(lexer_data);
+        lexer.advance();
+        lexer.advance();
+        lexer.tokenize_next(15, Category::Keyword);
+
+        let token = lexer.tokens.pop().unwrap();
+        let expected_token = Token{
+            lexeme: "égant".to_string(),
+            category: Category::Keyword,
+            start: 2,
+            end: 7,
+            span: Span{ start_line: 1, start_col: 3, end_line: 1, end_col: 8 },
+        };
+        assert_eq!(token, expected_token);
+    }
+
+    #[test]
+    fn tokenize_char_tokenizes_a_single_char() {
+        let lexer_data = "é(x";
+        let mut lexer = new(lexer_data);
+        lexer.advance();
+        lexer.tokenize_char(Category::Parenthesis);
+
+        assert_eq!(lexer.tokens.len(), 2);
+        assert_eq!(lexer.tokens[0].lexeme, "é");
+        assert_eq!(lexer.tokens[0].category, Category::Text);
+        assert_eq!(lexer.tokens[1].lexeme, "(");
+        assert_eq!(lexer.tokens[1].category, Category::Parenthesis);
+        assert_eq!(lexer.token_position, 2);
         assert_eq!(lexer.token_start, 2);
     }
 
     #[test]
-    fn tokenize_creates_the_correct_token() {
-        let lexer_data = "élégant";
+    fn tokenize_remaining_tokenizes_the_rest_of_the_data() {
+        let lexer_data = "a //élé\ngant";
         let mut lexer = new(lexer_data);
-        lexer.advance();
-        lexer.advance();
-        lexer.tokenize(Category::Text);
-        
+        lexer.advance_by(2);
+        lexer.tokenize_remaining(Category::Comment);
+
+        let expected_tokens = vec![
+            Token{
+                lexeme: "a ".to_string(),
+                category: Category::Text,
+                start: 0,
+                end: 2,
+                span: Span{ start_line: 1, start_col: 1, end_line: 1, end_col: 3 },
+            },
+            Token{
+                lexeme: "//élé\ngant".to_string(),
+                category: Category::Comment,
+                start: 2,
+                end: 12,
+                span: Span{ start_line: 1, start_col: 3, end_line: 2, end_col: 5 },
+            },
+        ];
+        assert_eq!(lexer.tokens, expected_tokens);
+        assert!(!lexer.has_more_data());
+
+        lexer.tokenize_remaining(Category::Comment);
+        assert_eq!(lexer.tokens.len(), 2);
+    }
+
+    #[test]
+    fn skip_whitespace_tokenizes_leading_spaces() {
+        let lexer_data = "  élégant";
+        let mut lexer = new(lexer_data);
+        lexer.skip_whitespace();
+
         let token = lexer.tokens.pop().unwrap();
-        let expected_token = Token{ lexeme: "él".to_string(), category: Category::Text};
+        let expected_token = Token{
+            lexeme: "  ".to_string(),
+            category: Category::Whitespace,
+            start: 0,
+            end: 2,
+            span: Span{ start_line: 1, start_col: 1, end_line: 1, end_col: 3 },
+        };
         assert_eq!(token, expected_token);
+        assert_eq!(lexer.token_start, 2);
     }
 
     #[test]
-    fn tokenize_does_nothing_if_range_is_empty() {
+    fn skip_whitespace_tokenizes_mixed_tabs_and_newlines() {
+        let lexer_data = "\t\n\télégant";
+        let mut lexer = new(lexer_data);
+        lexer.skip_whitespace();
+
+        let token = lexer.tokens.pop().unwrap();
+        let expected_token = Token{
+            lexeme: "\t\n\t".to_string(),
+            category: Category::Whitespace,
+            start: 0,
+            end: 3,
+            span: Span{ start_line: 1, start_col: 1, end_line: 2, end_col: 2 },
+        };
+        assert_eq!(token, expected_token);
+    }
+
+    #[test]
+    fn skip_whitespace_tokenizes_previous_data_as_text() {
+        let lexer_data = "él égant";
+        let mut lexer = new(lexer_data);
+        lexer.advance_by(2);
+        lexer.skip_whitespace();
+
+        let token = lexer.tokens.remove(0);
+        let expected_token = Token{
+            lexeme: "él".to_string(),
+            category: Category::Text,
+            start: 0,
+            end: 2,
+            span: Span{ start_line: 1, start_col: 1, end_line: 1, end_col: 3 },
+        };
+        assert_eq!(token, expected_token);
+    }
+
+    #[test]
+    fn skip_whitespace_does_nothing_if_there_is_no_whitespace() {
         let lexer_data = "élégant";
         let mut lexer = new(lexer_data);
-        lexer.tokenize(Category::Text);
-        
+        lexer.advance();
+        lexer.skip_whitespace();
+
         assert_eq!(lexer.tokens.len(), 0);
         assert_eq!(lexer.token_start, 0);
-        assert_eq!(lexer.token_position, 0);
+        assert_eq!(lexer.token_position, 1);
     }
 
     #[test]
-    fn tokenize_next_tokenizes_previous_data_as_text() {
+    fn normalize_merges_text_and_discards_empty_tokens() {
+        let mut lexer = new("fn élé gant x");
+        lexer.tokenize_next(2, Category::Keyword);
+        lexer.tokenize_next(1, Category::Text);
+        lexer.tokenize_next(3, Category::Text);
+        lexer.tokenize_next(1, Category::Whitespace);
+        lexer.tokenize_next(1, Category::Whitespace);
+        lexer.tokenize_next(3, Category::Text);
+        lexer.tokenize_next(2, Category::Text);
+        let mut empty_token = lexer.tokens[1].clone();
+        empty_token.lexeme.clear();
+        lexer.tokens.insert(2, empty_token.clone());
+        lexer.tokens.push(empty_token);
+        lexer.normalize();
+
+        let expected_tokens = vec![
+            Token{
+                lexeme: "fn".to_string(),
+                category: Category::Keyword,
+                start: 0,
+                end: 2,
+                span: Span{ start_line: 1, start_col: 1, end_line: 1, end_col: 3 },
+            },
+            Token{
+                lexeme: " élé".to_string(),
+                category: Category::Text,
+                start: 2,
+                end: 6,
+                span: Span{ start_line: 1, start_col: 3, end_line: 1, end_col: 7 },
+            },
+            Token{
+                lexeme: " ".to_string(),
+                category: Category::Whitespace,
+                start: 6,
+                end: 7,
+                span: Span{ start_line: 1, start_col: 7, end_line: 1, end_col: 8 },
+            },
+            Token{
+                lexeme: "g".to_string(),
+                category: Category::Whitespace,
+                start: 7,
+                end: 8,
+                span: Span{ start_line: 1, start_col: 8, end_line: 1, end_col: 9 },
+            },
+            Token{
+                lexeme: "ant x".to_string(),
+                category: Category::Text,
+                start: 8,
+                end: 13,
+                span: Span{ start_line: 1, start_col: 9, end_line: 1, end_col: 14 },
+            },
+        ];
+        assert_eq!(lexer.tokens, expected_tokens);
+    }
+
+    #[test]
+    fn tokenize_match_prefers_the_longest_candidate() {
+        let lexer_data = "a<=b";
+        let mut lexer = new(lexer_data);
+        lexer.advance();
+
+        assert!(lexer.tokenize_match(&["<", "<=", "="], Category::Operator));
+        assert_eq!(lexer.tokens[0].lexeme, "a");
+        assert_eq!(lexer.tokens[0].category, Category::Text);
+        assert_eq!(lexer.tokens[1].lexeme, "<=");
+        assert_eq!(lexer.tokens[1].category, Category::Operator);
+        assert_eq!(lexer.current_char(), Some('b'));
+    }
+
+    #[test]
+    fn tokenize_match_does_nothing_without_a_match() {
         let lexer_data = "élégant";
         let mut lexer = new(lexer_data);
         lexer.advance();
+
+        assert!(!lexer.tokenize_match(&["él", ""], Category::Keyword));
+        assert_eq!(lexer.tokens.len(), 0);
+        assert_eq!(lexer.token_position, 1);
+    }
+
+    #[test]
+    fn tokenize_string_tokenizes_a_quoted_string() {
+        let lexer_data = "x\"élégant\" y";
+        let mut lexer = new(lexer_data);
         lexer.advance();
-        lexer.tokenize_next(1, Category::Keyword);
 
-        let token = lexer.tokens.remove(0);
-        let expected_token = Token{ lexeme: "él".to_string(), category: Category::Text};
-        assert_eq!(token, expected_token);
+        assert!(lexer.tokenize_string('"', Category::String));
+        assert_eq!(lexer.tokens.len(), 2);
+        assert_eq!(lexer.tokens[0].lexeme, "x");
+        assert_eq!(lexer.tokens[0].category, Category::Text);
+        assert_eq!(lexer.tokens[1].lexeme, "\"élégant\"");
+        assert_eq!(lexer.tokens[1].category, Category::String);
+        assert_eq!(lexer.token_position, 10);
     }
 
     #[test]
-    fn tokenize_next_tokenizes_next_x_chars() {
+    fn tokenize_string_skips_escaped_quotes() {
+        let lexer_data = "\"a\\\"b\\\\\" c";
+        let mut lexer = new(lexer_data);
+
+        assert!(lexer.tokenize_string('"', Category::String));
+        assert_eq!(lexer.tokens[0].lexeme, "\"a\\\"b\\\\\"");
+    }
+
+    #[test]
+    fn tokenize_string_tokenizes_unterminated_strings_to_the_end() {
+        let lexer_data = "\"élégant\\\"";
+        let mut lexer = new(lexer_data);
+
+        assert!(!lexer.tokenize_string('"', Category::String));
+        assert_eq!(lexer.tokens[0].lexeme, lexer_data);
+        assert_eq!(lexer.token_position, lexer.char_count);
+    }
+
+    #[test]
+    fn tokenize_string_does_nothing_without_a_quote() {
         let lexer_data = "élégant";
         let mut lexer = new(lexer_data);
+
+        assert!(!lexer.tokenize_string('"', Category::String));
+        assert_eq!(lexer.tokens.len(), 0);
+        assert_eq!(lexer.token_position, 0);
+    }
+
+    #[test]
+    fn tokenize_line_comment_stops_at_a_newline() {
+        let lexer_data = "x # élégant\ny";
+        let mut lexer = new(lexer_data);
+        lexer.advance_by(2);
+
+        assert!(lexer.tokenize_line_comment("#", Category::Comment));
+        assert_eq!(lexer.tokens.len(), 2);
+        assert_eq!(lexer.tokens[0].lexeme, "x ");
+        assert_eq!(lexer.tokens[0].category, Category::Text);
+        assert_eq!(lexer.tokens[1].lexeme, "# élégant");
+        assert_eq!(lexer.tokens[1].category, Category::Comment);
+        assert_eq!(lexer.current_char(), Some('\n'));
+    }
+
+    #[test]
+    fn tokenize_line_comment_stops_when_there_is_no_more_data() {
+        let lexer_data = "// élégant";
+        let mut lexer = new(lexer_data);
+
+        assert!(lexer.tokenize_line_comment("//", Category::Comment));
+        assert_eq!(lexer.tokens[0].lexeme, lexer_data);
+        assert_eq!(lexer.token_position, lexer.char_count);
+    }
+
+    #[test]
+    fn tokenize_line_comment_does_nothing_without_the_prefix() {
+        let lexer_data = "/ élégant";
+        let mut lexer = new(lexer_data);
+
+        assert!(!lexer.tokenize_line_comment("//", Category::Comment));
+        assert!(!lexer.tokenize_line_comment("", Category::Comment));
+        assert_eq!(lexer.tokens.len(), 0);
+        assert_eq!(lexer.token_position, 0);
+    }
+
+    #[test]
+    fn tokenize_block_comment_ends_at_the_first_close_if_not_nested() {
+        let lexer_data = "<!-- <!-- é --> -->";
+        let mut lexer = new(lexer_data);
+
+        assert!(lexer.tokenize_block_comment("<!--", "-->", false, Category::Comment));
+        assert_eq!(lexer.tokens.len(), 1);
+        assert_eq!(lexer.tokens[0].lexeme, "<!-- <!-- é -->");
+        assert_eq!(lexer.tokens[0].category, Category::Comment);
+        assert_eq!(lexer.token_position, 15);
+    }
+
+    #[test]
+    fn tokenize_block_comment_tracks_the_depth_if_nested() {
+        let lexer_data = "x/* a /* é */ b */ */";
+        let mut lexer = new(lexer_data);
+        lexer.advance();
+
+        assert!(lexer.tokenize_block_comment("/*", "*/", true, Category::Comment));
+        assert_eq!(lexer.tokens.len(), 2);
+        assert_eq!(lexer.tokens[0].lexeme, "x");
+        assert_eq!(lexer.tokens[1].lexeme, "/* a /* é */ b */");
+        assert_eq!(lexer.tokens[1].category, Category::Comment);
+    }
+
+    #[test]
+    fn tokenize_block_comment_tokenizes_unterminated_comments_to_the_end() {
+        let lexer_data = "/* a /* é */";
+        let mut lexer = new(lexer_data);
+
+        assert!(!lexer.tokenize_block_comment("/*", "*/", true, Category::Comment));
+        assert_eq!(lexer.tokens[0].lexeme, lexer_data);
+        assert_eq!(lexer.token_position, lexer.char_count);
+    }
+
+    #[test]
+    fn tokenize_block_comment_does_nothing_without_the_opening_delimiter() {
+        let lexer_data = "*/ é";
+        let mut lexer = new(lexer_data);
+
+        assert!(!lexer.tokenize_block_comment("/*", "*/", false, Category::Comment));
+        assert_eq!(lexer.tokens.len(), 0);
+        assert_eq!(lexer.token_position, 0);
+    }
+
+    #[test]
+    fn tokenize_number_tokenizes_integers_and_fractions() {
+        for &number in ["42", "3.14", "+0.5"].iter() {
+            let mut lexer = new(number);
+
+            assert!(lexer.tokenize_number(Category::Integer));
+            assert_eq!(lexer.tokens.len(), 1);
+            assert_eq!(lexer.tokens[0].lexeme, number);
+            assert_eq!(lexer.tokens[0].category, Category::Integer);
+        }
+    }
+
+    #[test]
+    fn tokenize_number_tokenizes_exponents() {
+        let lexer_data = "1e-9 2E5 3e";
+        let mut lexer = new(lexer_data);
+
+        assert!(lexer.tokenize_number(Category::Float));
+        lexer.skip_whitespace();
+        assert!(lexer.tokenize_number(Category::Float));
+        lexer.skip_whitespace();
+        assert!(lexer.tokenize_number(Category::Float));
+
+        let lexemes: Vec<&str> = lexer.tokens.iter().map(|token| &token.lexeme[..]).collect();
+        assert_eq!(lexemes, vec!["1e-9", " ", "2E5", " ", "3"]);
+        assert_eq!(lexer.current_char(), Some('e'));
+    }
+
+    #[test]
+    fn tokenize_number_leaves_a_trailing_decimal_point() {
+        let lexer_data = "5.";
+        let mut lexer = new(lexer_data);
+
+        assert!(lexer.tokenize_number(Category::Integer));
+        assert_eq!(lexer.tokens[0].lexeme, "5");
+        assert_eq!(lexer.current_char(), Some('.'));
+    }
+
+    #[test]
+    fn tokenize_number_does_nothing_without_a_number() {
+        let lexer_data = "-é";
+        let mut lexer = new(lexer_data);
+
+        assert!(!lexer.tokenize_number(Category::Integer));
+        assert_eq!(lexer.tokens.len(), 0);
+        assert_eq!(lexer.token_position, 0);
+    }
+
+    #[test]
+    fn tokenize_identifier_tokenizes_ascii_identifiers() {
+        let lexer_data = "_luthor2+";
+        let mut lexer = new(lexer_data);
+        let is_start = |c: char| c.is_ascii_alphabetic() || c == '_';
+        let is_continue = |c: char| c.is_ascii_alphanumeric() || c == '_';
+
+        assert!(lexer.tokenize_identifier(is_start, is_continue, Category::Identifier));
+        assert_eq!(lexer.tokens.len(), 1);
+        assert_eq!(lexer.tokens[0].lexeme, "_luthor2");
+        assert_eq!(lexer.tokens[0].category, Category::Identifier);
+        assert_eq!(lexer.current_char(), Some('+'));
+    }
+
+    #[test]
+    fn tokenize_identifier_uses_the_given_predicates() {
+        let lexer_data = "font-size: é-1";
+        let mut lexer = new(lexer_data);
+        let is_continue = |c: char| c.is_alphanumeric() || c == '-';
+
+        assert!(lexer.tokenize_identifier(char::is_alphabetic, is_continue, Category::Property));
+        assert_eq!(lexer.tokens[0].lexeme, "font-size");
+        lexer.advance_by(2);
+        assert!(lexer.tokenize_identifier(char::is_alphabetic, is_continue, Category::Property));
+        assert_eq!(lexer.tokens[2].lexeme, "é-1");
+    }
+
+    #[test]
+    fn tokenize_identifier_rejects_invalid_first_chars() {
+        let lexer_data = "2fast";
+        let mut lexer = new(lexer_data);
+
+        assert!(!lexer.tokenize_identifier(char::is_alphabetic, char::is_alphanumeric, Category::Identifier));
+        assert_eq!(lexer.tokens.len(), 0);
+        assert_eq!(lexer.token_position, 0);
+    }
+
+    fn digits(lexer: &mut Tokenizer) -> Option<StateFunction> {
+        lexer.consume_while(|c| c.is_numeric());
+        lexer.tokenize(Category::Integer);
+        lexer.current_char().map(|_| StateFunction::new(letters))
+    }
+
+    fn letters(lexer: &mut Tokenizer) -> Option<StateFunction> {
+        lexer.consume_while(|c| c.is_alphabetic());
+        lexer.tokenize(Category::Identifier);
+        lexer.current_char().map(|_| StateFunction::new(digits))
+    }
+
+    #[test]
+    fn run_follows_state_functions_until_there_are_none() {
+        let mut lexer = new("123élé45gant");
+        lexer.run(StateFunction::new(digits));
+
+        let tokens: Vec<(String, Category)> = lexer.tokens().into_iter()
+            .map(|token| (token.lexeme, token.category)).collect();
+        assert_eq!(tokens, vec![
+            ("123".to_string(), Category::Integer),
+            ("élé".to_string(), Category::Identifier),
+            ("45".to_string(), Category::Integer),
+            ("gant".to_string(), Category::Identifier),
+        ]);
+    }
+
+    fn keyword_state(keywords: Rc<Vec<String>>) -> StateFunction {
+        StateFunction::new(move |lexer: &mut Tokenizer| {
+            lexer.skip_whitespace();
+            lexer.consume_until(|c| c.is_whitespace());
+            if keywords.iter().any(|keyword| keyword == lexer.current_lexeme()) {
+                lexer.tokenize(Category::Keyword);
+            } else {
+                lexer.tokenize(Category::Text);
+            }
+
+            if lexer.has_more_data() {
+                Some(keyword_state(keywords.clone()))
+            } else {
+                None
+            }
+        })
+    }
+
+    #[test]
+    fn run_accepts_closures_as_state_functions() {
+        let mut lexer = new("fn luthor() {}");
+        lexer.run(keyword_state(Rc::new(vec!["fn".to_string(), "{}".to_string()])));
+
+        let tokens: Vec<(String, Category)> = lexer.tokens().into_iter()
+            .map(|token| (token.lexeme, token.category)).collect();
+        assert_eq!(tokens, vec![
+            ("fn".to_string(), Category::Keyword),
+            (" ".to_string(), Category::Whitespace),
+            ("luthor()".to_string(), Category::Text),
+            (" ".to_string(), Category::Whitespace),
+            ("{}".to_string(), Category::Keyword),
+        ]);
+    }
+
+    fn string(lexer: &mut Tokenizer) -> Option<StateFunction> {
+        lexer.advance();
+        lexer.consume_until(|c| c == '"');
+        if lexer.has_more_data() {
+            lexer.advance();
+        } else {
+            lexer.tokenize_error("unterminated string");
+        }
+        lexer.tokenize(Category::String);
+        None
+    }
+
+    #[test]
+    fn tokenize_error_records_errors_without_interrupting_lexing() {
+        let mut lexer = new("\"élégant");
+        lexer.run(StateFunction::new(string));
+
+        assert_eq!(lexer.errors(), &[LexError{ message: "unterminated string".to_string(), position: 8 }]);
+        assert_eq!(lexer.tokens.len(), 1);
+        assert_eq!(lexer.tokens[0].lexeme, "\"élégant");
+        assert_eq!(lexer.tokens[0].category, Category::String);
+    }
+
+    #[test]
+    fn terminated_strings_do_not_record_errors() {
+        let mut lexer = new("\"élégant\"");
+        lexer.run(StateFunction::new(string));
+
+        assert!(lexer.errors().is_empty());
+        assert_eq!(lexer.tokens[0].lexeme, "\"élégant\"");
+    }
+
+    #[test]
+    fn methods_tolerate_a_cursor_past_the_end_of_the_data() {
+        for &position in [8, usize::MAX].iter() {
+            let mut lexer = new("élé\ngant");
+            lexer.advance_by(2);
+            lexer.token_position = position;
+
+            assert!(!lexer.has_more_data());
+            assert_eq!(lexer.current_char(), None);
+            assert_eq!(lexer.peek_char(), None);
+            assert_eq!(lexer.peek(usize::MAX), None);
+            assert_eq!(lexer.remaining(), "");
+            assert_eq!(lexer.remaining_len(), 0);
+            assert_eq!(lexer.current_lexeme(), "élé\ngant");
+            assert_eq!(lexer.byte_offset(), lexer.data.len());
+            assert!(!lexer.starts_with("t"));
+
+            lexer.advance();
+            lexer.backup();
+            lexer.backup();
+            lexer.consume_while(|_| true);
+            lexer.advance_to('x');
+            lexer.skip_whitespace();
+            lexer.tokenize(Category::Text);
+            lexer.tokenize_next(3, Category::Text);
+            assert_eq!(lexer.reconstruct(), "élé\ngant");
+
+            lexer.seek(1);
+            assert_eq!(lexer.current_char(), Some('l'));
+        }
+    }
+
+    #[test]
+    fn methods_tolerate_a_token_start_past_the_cursor() {
+        let mut lexer = new("élégant");
         lexer.advance();
+        lexer.token_start = usize::MAX;
+
+        assert_eq!(lexer.current_lexeme(), "");
+        assert_eq!(lexer.pending_len(), 0);
+        assert_eq!(lexer.preview_token(Category::Text), None);
+        lexer.backup();
+        lexer.tokenize_next(2, Category::Keyword);
+        assert!(lexer.tokens.is_empty());
+
+        lexer.token_start = 1;
+        lexer.tokenize(Category::Keyword);
+        assert_eq!(lexer.tokens[0].lexeme, "lé");
+    }
+
+    #[test]
+    fn methods_tolerate_data_replaced_without_a_reset() {
+        let mut lexer = new("élégant");
         lexer.advance();
-        lexer.tokenize_next(5, Category::Keyword);
+        lexer.data = "x".to_string();
 
-        let token = lexer.tokens.pop().unwrap();
-        let expected_token = Token{ lexeme: "égant".to_string(), category: Category::Keyword};
-        assert_eq!(token, expected_token);
+        assert_eq!(lexer.current_char(), None);
+        assert_eq!(lexer.current_lexeme(), "");
+        assert_eq!(lexer.remaining(), "");
+        lexer.advance_by(10);
+        lexer.backup();
+        lexer.seek(3);
+        lexer.tokenize_next(3, Category::Text);
+
+        lexer.reset("élé");
+        lexer.advance();
+        assert_eq!(lexer.current_char(), Some('l'));
     }
 
     #[test]
-    fn tokenize_next_takes_at_most_what_is_left() {
+    fn reconstruct_reproduces_the_data_after_running_a_lexer() {
+        let lexer_data = "123élé45gant";
+        let mut lexer = new(lexer_data);
+        lexer.run(StateFunction::new(digits));
+
+        assert_eq!(lexer.reconstruct(), lexer.data);
+    }
+
+    #[test]
+    fn tokens_with_spans_cover_the_data_contiguously() {
+        let lexer_data = "123élé45gant";
+        let mut lexer = new(lexer_data);
+        lexer.run(StateFunction::new(digits));
+
+        let mut end = 0;
+        for (token, range) in lexer.tokens_with_spans() {
+            assert_eq!(range.start, end);
+            assert_eq!(range.len(), token.len());
+            end = range.end;
+        }
+        assert_eq!(end, lexer.data_len());
+    }
+
+    #[test]
+    fn reconstruct_omits_ignored_data() {
         let lexer_data = "élégant";
         let mut lexer = new(lexer_data);
+        lexer.advance_by(2);
+        lexer.ignore();
+        lexer.advance_by(5);
+        lexer.tokenize(Category::Text);
+
+        assert_eq!(lexer.reconstruct(), "égant");
+    }
+
+    #[test]
+    fn token_stream_yields_the_same_tokens_as_run() {
+        let mut lexer = new("123élé45gant");
+        lexer.run(StateFunction::new(digits));
+
+        let stream = TokenStream::new(new("123élé45gant"), StateFunction::new(digits));
+        assert_eq!(stream.collect::<Vec<Token>>(), lexer.tokens);
+    }
+
+    #[test]
+    fn token_stream_only_lexes_as_much_as_is_needed() {
+        let mut stream = TokenStream::new(new("123élé45gant"), StateFunction::new(digits));
+        let tokens: Vec<String> = stream.by_ref().take(2).map(|token| token.lexeme).collect();
+
+        assert_eq!(tokens, vec!["123", "élé"]);
+        assert_eq!(stream.tokenizer.token_position, 6);
+        assert!(stream.tokenizer.has_more_data());
+        assert_eq!(stream.next().unwrap().lexeme, "45");
+    }
+
+    #[test]
+    fn token_stream_keeps_tokens_available_to_states() {
+        fn digits_after_letters(lexer: &mut Tokenizer) -> Option<StateFunction> {
+            lexer.consume_while(|c| c.is_numeric());
+            let category = match lexer.last_token() {
+                Some(token) if token.category == Category::Identifier => Category::Integer,
+                _ => Category::Text,
+            };
+            lexer.tokenize(category);
+            lexer.consume_while(|c| c.is_alphabetic());
+            lexer.tokenize(Category::Identifier);
+            lexer.current_char().map(|_| StateFunction::new(digits_after_letters))
+        }
+
+        let stream = TokenStream::new(new("12ab34"), StateFunction::new(digits_after_letters));
+        let categories: Vec<Category> = stream.map(|token| token.category).collect();
+
+        assert_eq!(categories, vec![Category::Text, Category::Identifier, Category::Integer]);
+    }
+
+    #[test]
+    #[cfg(feature = "unicode-segmentation")]
+    fn with_graphemes_advances_over_grapheme_clusters() {
+        let family = "\u{1f468}\u{200d}\u{1f469}\u{200d}\u{1f467}";
+        let lexer_data = format!("e\u{301}{}\r\nx", family);
+        let mut lexer: Tokenizer = Tokenizer::with_graphemes(&lexer_data);
+        assert_eq!(lexer.char_count, 4);
+
         lexer.advance();
+        lexer.tokenize(Category::Text);
         lexer.advance();
-        lexer.tokenize_next(15, Category::Keyword);
+        assert_eq!(lexer.current_lexeme(), family);
+        lexer.tokenize(Category::Text);
+        lexer.advance();
+        assert_eq!(lexer.position(), (2, 1));
+        lexer.backup();
+        assert_eq!(lexer.position(), (1, 3));
+        lexer.advance_by(2);
+        lexer.tokenize(Category::Text);
 
-        let token = lexer.tokens.pop().unwrap();
-        let expected_token = Token{ lexeme: "égant".to_string(), category: Category::Keyword};
-        assert_eq!(token, expected_token);
+        let expected_tokens = vec![
+            Token{
+                lexeme: "e\u{301}".to_string(),
+                category: Category::Text,
+                start: 0,
+                end: 1,
+                span: Span{ start_line: 1, start_col: 1, end_line: 1, end_col: 2 },
+            },
+            Token{
+                lexeme: family.to_string(),
+                category: Category::Text,
+                start: 1,
+                end: 2,
+                span: Span{ start_line: 1, start_col: 2, end_line: 1, end_col: 3 },
+            },
+            Token{
+                lexeme: "\r\nx".to_string(),
+                category: Category::Text,
+                start: 2,
+                end: 4,
+                span: Span{ start_line: 1, start_col: 3, end_line: 2, end_col: 2 },
+            },
+        ];
+        assert_eq!(lexer.tokens, expected_tokens);
+    }
+
+    #[test]
+    #[cfg(feature = "unicode-segmentation")]
+    fn with_graphemes_retains_grapheme_units_after_reset() {
+        let mut lexer: Tokenizer = Tokenizer::with_graphemes("luthor");
+        lexer.reset("a\u{301}b");
+        lexer.seek(1);
+
+        assert_eq!(lexer.current_char(), Some('b'));
+        assert_eq!(lexer.position(), (1, 2));
     }
 }